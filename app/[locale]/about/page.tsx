@@ -6,6 +6,7 @@ import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/com
 import { Button } from '@/components/ui/button';
 import { useTranslations } from 'next-intl'
 import { open } from '@tauri-apps/plugin-shell'
+import { formatError } from '@/lib/errors'
 import { ExternalLink, Info, FileText, Monitor, Cpu, MemoryStick, Puzzle, Clapperboard, Package, BadgeInfo, RefreshCw } from 'lucide-react'
 
 export default function AboutPage() {
@@ -49,7 +50,7 @@ export default function AboutPage() {
       .then((res) => setVulkanInfo(res as any))
       .catch((e) => {
         console.error('Failed to get Vulkan support info:', e);
-        setVulkanInfo({ supported: false, device_count: 0, api_version: null, error: String(e) });
+        setVulkanInfo({ supported: false, device_count: 0, api_version: null, error: formatError(e) });
       });
   }, []);
 
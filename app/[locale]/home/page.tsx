@@ -7,7 +7,7 @@ import { Switch } from '@/components/ui/switch';
 import { Slider } from '@/components/ui/slider';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '@/components/ui/select';
 import { useProcessing } from '@/contexts/ProcessingContext';
-import { Folder, FileVideo, FileAudio, Mic, Timer as TimerIcon, Save, Copy as CopyIcon, StopCircle, Globe, Bot } from 'lucide-react'
+import { Folder, FileVideo, FileAudio, Mic, Timer as TimerIcon, Save, Copy as CopyIcon, StopCircle, Pause, Play, Globe, Bot } from 'lucide-react'
 import { 
   AlertDialog,
   AlertDialogTrigger,
@@ -17,13 +17,18 @@ import {
   AlertDialogDescription,
   AlertDialogFooter
 } from '@/components/ui/alert-dialog';
-import {useTranslations} from 'next-intl'
+import {useLocale, useTranslations} from 'next-intl'
+import { useRouter } from 'next/navigation'
 import { useSettingsStore } from '@/hooks/settingsStore'
+import { errorCode, formatError } from '@/lib/errors'
 
 export default function HomePage() {
   const t = useTranslations('Home')
   const tSettings = useTranslations('Settings')
-  const { state, updateState, resetState, setProcessingFile, startTimer, stopTimer, resetTimer } = useProcessing();
+  const tErrors = useTranslations('Errors')
+  const locale = useLocale()
+  const router = useRouter()
+  const { state, updateState, resetState, setProcessingFile, holdJob, startTimer, stopTimer, resetTimer } = useProcessing();
   const {
     selectedFile,
     currentAudioPath,
@@ -36,6 +41,8 @@ export default function HomePage() {
     totalDuration,
     currentProgress,
     progressPercentage,
+    etaSeconds,
+    whisperStatus,
   } = state;
   
   const [isDragging, setIsDragging] = useState(false);
@@ -50,6 +57,8 @@ export default function HomePage() {
   } = useSettingsStore()
   const whisperOutputRef = useRef<HTMLDivElement>(null);
   const [stopDialogOpen, setStopDialogOpen] = useState(false);
+  // 本机逻辑核心数与自动模式下将使用的线程数
+  const [threadInfo, setThreadInfo] = useState<{ maxThreads: number; autoThreads: number } | null>(null);
 
   // 语言选项（复用设置页的本地化）
   const languageOptions = [
@@ -145,10 +154,12 @@ export default function HomePage() {
       console.log('模型文件存在，继续处理...');
     } catch (error) {
       console.error('检查模型失败:', error);
-      const errorMessage = typeof error === 'string' ? error : String(error);
+      const errorMessage = formatError(error, tErrors);
       
-      if (errorMessage.includes('请在设置中配置') && settings.whisper_model !== EMBEDDED_MODEL) {
-        alert('❌ 请先在设置页面配置 Whisper Models 路径！');
+      if (errorCode(error) === 'models_path_not_configured' && settings.whisper_model !== EMBEDDED_MODEL) {
+        if (confirm(`❌ ${errorMessage}\n\n${tErrors('openSettings')}`)) {
+          router.push(`/${locale}/settings`);
+        }
       } else {
         alert(`❌ 检查模型失败：${errorMessage}\n\n请检查：\n• Models 路径是否正确配置\n• 是否有访问权限`);
       }
@@ -184,6 +195,7 @@ export default function HomePage() {
       
       const audioPath = (result as any).output_path;
       const duration = (result as any).duration_seconds;
+      holdJob((result as any).job_id);
       if (audioPath) {
         updateState({ 
           currentAudioPath: audioPath,
@@ -194,7 +206,7 @@ export default function HomePage() {
     } catch (error) {
       console.error('处理失败:', error);
       updateState({
-        processResult: `处理失败：${error}`,
+        processResult: `处理失败：${formatError(error, tErrors)}`,
         isProcessing: false,
       });
     }
@@ -217,8 +229,12 @@ export default function HomePage() {
       });
     } catch (error) {
       console.error('Whisper 识别失败:', error);
+      // 模型缺失时引导到模型页面下载
+      if (errorCode(error) === 'model_not_found' && confirm(`${formatError(error, tErrors)}\n\n${tErrors('openModels')}`)) {
+        router.push(`/${locale}/models`);
+      }
       updateState({
-        processResult: `Whisper 识别失败：${error}`,
+        processResult: `Whisper 识别失败：${formatError(error, tErrors)}`,
         isWhisperRunning: false,
         isProcessing: false,
       });
@@ -235,6 +251,16 @@ export default function HomePage() {
     }
   };
 
+  // 暂停/恢复当前识别，状态由 whisper-status 事件同步
+  const toggleWhisperPause = async () => {
+    try {
+      await invoke(whisperStatus?.user_paused ? 'resume_job' : 'pause_job');
+    } catch (error) {
+      console.error('暂停或恢复 Whisper 失败:', error);
+      alert(formatError(error, tErrors));
+    }
+  };
+
   const copyWhisperOutput = () => {
     const text = whisperOutput.join('\n');
     navigator.clipboard.writeText(text).then(() => {
@@ -289,7 +315,7 @@ export default function HomePage() {
       alert(`SRT 字幕文件已保存到：\n${savedPath}`);
     } catch (error) {
       console.error('保存 SRT 文件失败:', error);
-      alert(`保存 SRT 文件失败：${error}`);
+      alert(`保存 SRT 文件失败：${formatError(error, tErrors)}`);
     }
   };
 
@@ -313,7 +339,7 @@ export default function HomePage() {
       alert(`LRC 歌词文件已保存到：\n${savedPath}`);
     } catch (error) {
       console.error('保存 LRC 文件失败:', error);
-      alert(`保存 LRC 文件失败：${error}`);
+      alert(`保存 LRC 文件失败：${formatError(error, tErrors)}`);
     }
   };
   
@@ -346,7 +372,7 @@ export default function HomePage() {
   };
 
   const changeThreads = async (vals: number[]) => {
-    const n = Math.max(1, Math.min(8, Math.round(vals[0] ?? 4)));
+    const n = Math.max(0, Math.round(vals[0] ?? 0));
     setThreadCount(n, 'debounced')
   };
 
@@ -364,6 +390,12 @@ export default function HomePage() {
     checkModelStatus();
   }, [settings.whisper_model, settings.whisper_models_path]);
 
+  useEffect(() => {
+    invoke('get_thread_info')
+      .then((info: any) => setThreadInfo({ maxThreads: info.cpu.logical_cores, autoThreads: info.plan.threads }))
+      .catch(error => console.error('获取 CPU 信息失败:', error));
+  }, [settings.processor_count]);
+
   const handleDragOver = (e: React.DragEvent) => {
     e.preventDefault();
     setIsDragging(true);
@@ -423,9 +455,9 @@ export default function HomePage() {
               <span className="text-sm text-gray-600 dark:text-gray-300">{t('vad')}</span>
               <Switch checked={!!settings.enable_vad} onCheckedChange={toggleVad} disabled={locked} />
               <div className="flex items-center gap-3">
-                <span className="text-sm text-gray-600 dark:text-gray-300">{t('threads')}: {settings.thread_count}</span>
+                <span className="text-sm text-gray-600 dark:text-gray-300">{t('threads')}: {settings.thread_count === 0 ? t('threadsAuto', { count: threadInfo?.autoThreads ?? '-' }) : settings.thread_count}</span>
                 <div className={locked ? 'pointer-events-none opacity-60' : ''}>
-                  <Slider min={1} max={8} step={1} value={[settings.thread_count]} onValueChange={changeThreads} />
+                  <Slider min={0} max={threadInfo?.maxThreads ?? 8} step={1} value={[settings.thread_count]} onValueChange={changeThreads} />
                 </div>
               </div>
             </div>
@@ -529,7 +561,12 @@ export default function HomePage() {
                     ) : (
                       <span></span>
                     )}
-                    <span>{progressPercentage.toFixed(1)}%</span>
+                    <span>
+                      {progressPercentage.toFixed(1)}%
+                      {etaSeconds !== null && !whisperStatus?.user_paused && !whisperStatus?.paused && (
+                        <> · {t('eta', { time: formatTime(etaSeconds) })}</>
+                      )}
+                    </span>
                     {totalDuration && totalDuration > 0 ? (
                       <span>{formatTime(totalDuration)}</span>
                     ) : (
@@ -542,6 +579,22 @@ export default function HomePage() {
                       style={{ width: `${Math.min(progressPercentage, 100)}%` }}
                     ></div>
                   </div>
+                  {whisperStatus && (whisperStatus.background_priority || whisperStatus.cpu_cap_percent > 0 || whisperStatus.user_paused) && (
+                    <div className="flex justify-center gap-3 text-xs text-gray-600 dark:text-gray-400 mt-2">
+                      {whisperStatus.background_priority && <span>{t('backgroundPriority')}</span>}
+                      {whisperStatus.cpu_cap_percent > 0 && (
+                        <span>{t('cpuCap', { percent: whisperStatus.cpu_cap_percent })}</span>
+                      )}
+                      {whisperStatus.user_paused && (
+                        <span className="text-amber-600 dark:text-amber-400">{t('pausedByUser')}</span>
+                      )}
+                      {whisperStatus.paused && (
+                        <span className="text-amber-600 dark:text-amber-400">
+                          {t('pausedForLoad', { load: whisperStatus.other_load_percent.toFixed(0) })}
+                        </span>
+                      )}
+                    </div>
+                  )}
                 </div>
               )}
             </div>
@@ -555,6 +608,19 @@ export default function HomePage() {
                 <Mic className="w-5 h-5" /> {t('speechResults')}
               </h3>
               <div className="flex gap-2">
+                {isWhisperRunning && whisperStatus && (
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={toggleWhisperPause}
+                  >
+                    {whisperStatus.user_paused ? (
+                      <><Play className="w-4 h-4 mr-1" /> {t('resume')}</>
+                    ) : (
+                      <><Pause className="w-4 h-4 mr-1" /> {t('pause')}</>
+                    )}
+                  </Button>
+                )}
                 {isWhisperRunning && (
                   <AlertDialog open={stopDialogOpen} onOpenChange={setStopDialogOpen}>
                     <AlertDialogTrigger asChild>
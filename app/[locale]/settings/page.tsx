@@ -5,16 +5,19 @@ import { invoke } from "@tauri-apps/api/core";
 import { Button } from '@/components/ui/button';
 import { Input } from '@/components/ui/input';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '@/components/ui/select';
+import { Switch } from '@/components/ui/switch';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
 import {useLocale, useTranslations} from 'next-intl'
 import {usePathname, useRouter} from 'next/navigation'
 import { Languages, Settings as SettingsIcon, SunMoon, Bot, Globe, Folder, FolderOpen, RefreshCw, Gauge } from 'lucide-react'
 import { useSettingsStore } from '@/hooks/settingsStore'
+import { formatError } from '@/lib/errors'
 
 export default function SettingsPage() {
   const locale = useLocale();
   const tHeader = useTranslations('Header');
   const t = useTranslations('Settings');
+  const tErrors = useTranslations('Errors');
   const pathname = usePathname();
   const router = useRouter();
   const {
@@ -24,6 +27,8 @@ export default function SettingsPage() {
     setLocale: setAppLocale,
     setWhisperLanguage,
     setOptimization,
+    setBackgroundPriority,
+    setCpuCap,
     chooseModelsDirectory
   } = useSettingsStore()
   
@@ -52,6 +57,7 @@ export default function SettingsPage() {
     path: string;
     size_bytes: number;
     size_formatted: string;
+    portable: { dir: string; source: 'flag' | 'env' | 'marker' } | null;
   } | null>(null);
   const [isLoadingAppData, setIsLoadingAppData] = useState(false);
 
@@ -74,7 +80,7 @@ export default function SettingsPage() {
       await invoke('open_app_data_directory');
     } catch (error) {
       console.error('打开目录失败:', error);
-      alert(`打开目录失败: ${error}`);
+      alert(`打开目录失败: ${formatError(error, tErrors)}`);
     }
   };
 
@@ -293,6 +299,14 @@ export default function SettingsPage() {
                           {appDataInfo.size_formatted}
                         </span>
                       </div>
+                      {appDataInfo.portable && (
+                        <div className="flex items-center justify-between">
+                          <span className="text-sm text-gray-600 dark:text-gray-400">{t('portableMode')}</span>
+                          <span className="text-sm font-medium text-gray-800 dark:text-gray-200">
+                            {t(`portableSource.${appDataInfo.portable.source}`)}
+                          </span>
+                        </div>
+                      )}
                     </div>
                   </div>
                   
@@ -361,6 +375,32 @@ export default function SettingsPage() {
                 {t('optimizeHint')}
               </div>
             </div>
+            <div className="space-y-2 mt-6">
+              <div className="flex items-center justify-between">
+                <label className="text-sm text-gray-600 dark:text-gray-300">{t('backgroundPriority')}</label>
+                <Switch
+                  checked={!!settings.background_priority}
+                  onCheckedChange={(v) => setBackgroundPriority(v, 'debounced')}
+                />
+              </div>
+              <div className="text-xs text-gray-500 dark:text-gray-400">
+                {t('backgroundPriorityHint')}
+              </div>
+            </div>
+            <div className="space-y-2 mt-6">
+              <label className="text-sm text-gray-600 dark:text-gray-300">{t('cpuCap')}</label>
+              <Input
+                type="number"
+                min={0}
+                max={100}
+                className="w-32"
+                value={settings.cpu_cap_percent}
+                onChange={(e) => setCpuCap(Number(e.target.value), 'debounced')}
+              />
+              <div className="text-xs text-gray-500 dark:text-gray-400">
+                {t('cpuCapHint')}
+              </div>
+            </div>
           </CardContent>
         </Card>
 
@@ -3,6 +3,7 @@
 import { createContext, useContext, useState, useEffect, ReactNode, useRef } from 'react';
 import { useTranslations } from 'next-intl'
 import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/core';
 
 type SelectedFileInfo = {
   name: string;
@@ -12,6 +13,16 @@ type SelectedFileInfo = {
   blob?: File | null;
 };
 
+// 识别进程的调度状态（后台优先级、CPU 上限暂停）
+export type WhisperStatus = {
+  background_priority: boolean;
+  cpu_cap_percent: number;
+  paused: boolean;
+  user_paused: boolean;
+  other_load_percent: number;
+  paused_seconds: number;
+};
+
 interface ProcessingState {
   // 文件相关状态
   selectedFile: SelectedFileInfo | null;
@@ -33,6 +44,11 @@ interface ProcessingState {
   totalDuration: number | null;
   currentProgress: number;
   progressPercentage: number;
+  // 预计剩余秒数（不含暂停时间），进度不足时为 null
+  etaSeconds: number | null;
+
+  // 调度状态，未在识别时为 null
+  whisperStatus: WhisperStatus | null;
 }
 
 interface ProcessingContextType {
@@ -41,6 +57,7 @@ interface ProcessingContextType {
   resetState: () => void;
   addWhisperOutput: (output: string) => void;
   setProcessingFile: (file: SelectedFileInfo | null) => void;
+  holdJob: (jobId: string | null | undefined) => void;
   startTimer: () => void;
   stopTimer: () => void;
   resetTimer: () => void;
@@ -58,6 +75,8 @@ const initialState: ProcessingState = {
   totalDuration: null,
   currentProgress: 0,
   progressPercentage: 0,
+  etaSeconds: null,
+  whisperStatus: null,
 };
 
 const ProcessingContext = createContext<ProcessingContextType | undefined>(undefined);
@@ -66,6 +85,10 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
   const [state, setState] = useState<ProcessingState>(initialState);
   const timerRef = useRef<NodeJS.Timeout | null>(null);
   const startTimeRef = useRef<number | null>(null);
+  // 暂停开始的时间，暂停期间计时器停止
+  const pausedAtRef = useRef<number | null>(null);
+  // 当前转换结果所在的任务，持有期间后端清理时会跳过该任务目录
+  const jobIdRef = useRef<string | null>(null);
   const t = useTranslations('Processing')
 
   // 更新状态的函数
@@ -73,8 +96,25 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
     setState(prev => ({ ...prev, ...updates }));
   };
 
+  // 释放当前持有的任务（结果被替换或丢弃时）
+  const releaseJob = () => {
+    const jobId = jobIdRef.current;
+    jobIdRef.current = null;
+    if (jobId) {
+      invoke('release_job', { jobId }).catch(error => console.error('释放任务失败:', error));
+    }
+  };
+
+  // 持有新转换的任务，同时释放之前的任务
+  const holdJob = (jobId: string | null | undefined) => {
+    if (jobIdRef.current === (jobId ?? null)) return;
+    releaseJob();
+    jobIdRef.current = jobId ?? null;
+  };
+
   // 重置状态的函数
   const resetState = () => {
+    releaseJob();
     setState(initialState);
   };
 
@@ -91,16 +131,12 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
 
   // 设置处理文件的函数
   const setProcessingFile = (file: SelectedFileInfo | null) => {
+    releaseJob();
     setState(prev => ({ ...prev, selectedFile: file }));
   };
 
-  // 开始计时器的函数
-  const startTimer = () => {
-    const startTime = Date.now();
-    startTimeRef.current = startTime;
-    setState(prev => ({ ...prev, recognitionElapsedTime: 0 }));
-    
-    // 启动计时器
+  // 启动计时器的定时刷新
+  const runTimer = () => {
     timerRef.current = setInterval(() => {
       if (startTimeRef.current) {
         setState(prev => ({ ...prev, recognitionElapsedTime: Date.now() - startTimeRef.current! }));
@@ -108,6 +144,30 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
     }, 100); // 每100ms更新一次
   };
 
+  // 开始计时器的函数
+  const startTimer = () => {
+    const startTime = Date.now();
+    startTimeRef.current = startTime;
+    pausedAtRef.current = null;
+    setState(prev => ({ ...prev, recognitionElapsedTime: 0 }));
+    runTimer();
+  };
+
+  // 暂停计时（识别进程被挂起时）
+  const pauseTimer = () => {
+    if (!timerRef.current) return;
+    stopTimer();
+    pausedAtRef.current = Date.now();
+  };
+
+  // 恢复计时，暂停的时间不计入
+  const resumeTimer = () => {
+    if (pausedAtRef.current === null || !startTimeRef.current) return;
+    startTimeRef.current += Date.now() - pausedAtRef.current;
+    pausedAtRef.current = null;
+    runTimer();
+  };
+
   // 停止计时器的函数（保留当前时间）
   const stopTimer = () => {
     if (timerRef.current) {
@@ -120,6 +180,7 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
   const resetTimer = () => {
     stopTimer();
     startTimeRef.current = null;
+    pausedAtRef.current = null;
     setState(prev => ({ ...prev, recognitionElapsedTime: 0 }));
   };
 
@@ -135,11 +196,13 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
             current_seconds: number;
             total_seconds: number;
             percentage: number;
+            eta_seconds: number | null;
           };
           updateState({
             currentProgress: progressInfo.current_seconds,
             progressPercentage: progressInfo.percentage,
             totalDuration: progressInfo.total_seconds,
+            etaSeconds: progressInfo.eta_seconds ?? null,
           });
         });
 
@@ -193,7 +256,19 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
           });
         });
 
-        unlistenFunctions = [unlistenProgress, unlistenOutput, unlistenComplete, unlistenError, unlistenStopped];
+        // 监听调度状态（优先级、暂停/恢复）
+        // 进程被挂起（手动暂停或 CPU 上限）时停止计时；whisper-paused / whisper-resumed 随后也会更新状态
+        const unlistenStatus = await listen('whisper-status', (event) => {
+          const status = event.payload as WhisperStatus | null;
+          if (status && (status.paused || status.user_paused)) {
+            pauseTimer();
+          } else if (status) {
+            resumeTimer();
+          }
+          updateState({ whisperStatus: status });
+        });
+
+        unlistenFunctions = [unlistenProgress, unlistenOutput, unlistenComplete, unlistenError, unlistenStopped, unlistenStatus];
       } catch (error) {
         console.error('设置事件监听器失败:', error);
       }
@@ -218,6 +293,7 @@ export function ProcessingProvider({ children }: { children: ReactNode }) {
       resetState,
       addWhisperOutput,
       setProcessingFile,
+      holdJob,
       startTimer,
       stopTimer,
       resetTimer,
@@ -2,6 +2,7 @@
 
 import {create} from 'zustand'
 import {invoke} from '@tauri-apps/api/core'
+import {formatError} from '@/lib/errors'
 
 export type WhisperOptimization = 'none' | 'vulkan' | 'coreml' | 'cuda'
 export type AppLocale = 'zh-CN' | 'en'
@@ -14,7 +15,12 @@ export interface Settings {
   enable_vad: boolean
   whisper_optimization: WhisperOptimization
   disable_gpu: boolean
+  // 0 = auto (picked from physical cores and current load)
   thread_count: number
+  processor_count: number
+  background_priority: boolean
+  // 0 = no cap; otherwise pause recognition while other programs use more CPU than this
+  cpu_cap_percent: number
 }
 
 const DEFAULTS: Settings = {
@@ -25,7 +31,10 @@ const DEFAULTS: Settings = {
   enable_vad: false,
   whisper_optimization: 'none',
   disable_gpu: false,
-  thread_count: 4,
+  thread_count: 0,
+  processor_count: 1,
+  background_priority: false,
+  cpu_cap_percent: 0,
 }
 
 type SaveMode = 'immediate' | 'debounced' | 'manual'
@@ -47,6 +56,8 @@ interface SettingsStore {
   setDisableGpu: (disabled: boolean, mode?: SaveMode) => void
   setThreadCount: (n: number, mode?: SaveMode) => void
   setOptimization: (opt: WhisperOptimization, mode?: SaveMode) => void
+  setBackgroundPriority: (enabled: boolean, mode?: SaveMode) => void
+  setCpuCap: (percent: number, mode?: SaveMode) => void
   setModelsPath: (p: string | null, mode?: SaveMode) => void
   setModelName: (name: string, mode?: SaveMode) => void
   chooseModelsDirectory: () => Promise<void>
@@ -69,7 +80,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       const next: Settings = {...DEFAULTS, ...(loaded as any)}
       set({settings: next, isLoading: false})
     } catch (e) {
-      set({isLoading: false, error: String(e)})
+      set({isLoading: false, error: formatError(e)})
     }
   },
 
@@ -78,7 +89,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       const s = get().settings
       await invoke('save_settings', {settings: s})
     } catch (e) {
-      set({error: String(e)})
+      set({error: formatError(e)})
     }
   },
 
@@ -106,10 +117,15 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
   setVad: (enabled, mode) => get().update({enable_vad: enabled}, mode),
   setDisableGpu: (disabled, mode) => get().update({disable_gpu: disabled}, mode),
   setThreadCount: (n, mode) => {
-    const clamped = Math.max(1, Math.min(8, Math.round(n)))
-    get().update({thread_count: clamped}, mode)
+    // Upper bound is the machine's logical core count, enforced by the backend
+    get().update({thread_count: Math.max(0, Math.round(n))}, mode)
   },
   setOptimization: (opt, mode) => get().update({whisper_optimization: opt}, mode),
+  setBackgroundPriority: (enabled, mode) => get().update({background_priority: enabled}, mode),
+  setCpuCap: (percent, mode) => {
+    const n = Math.round(Number.isFinite(percent) ? percent : 0)
+    get().update({cpu_cap_percent: Math.min(100, Math.max(0, n))}, mode)
+  },
   setModelsPath: (p, mode) => get().update({whisper_models_path: p}, mode),
   setModelName: (name, mode) => get().update({whisper_model: name}, mode),
 
@@ -120,7 +136,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
         get().setModelsPath(dir, 'immediate')
       }
     } catch (e) {
-      set({error: String(e)})
+      set({error: formatError(e)})
     }
   },
 }))
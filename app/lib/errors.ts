@@ -0,0 +1,31 @@
+// 后端命令返回的结构化错误：{ code, params, detail }
+export type MurmurError = {
+  code: string
+  params: Record<string, string>
+  detail: string | null
+}
+
+type Translator = {
+  (key: string, values?: Record<string, string>): string
+  has: (key: string) => boolean
+}
+
+export function isMurmurError(error: unknown): error is MurmurError {
+  return typeof error === 'object' && error !== null && typeof (error as any).code === 'string'
+}
+
+export function errorCode(error: unknown): string | undefined {
+  return isMurmurError(error) ? error.code : undefined
+}
+
+// 按错误码本地化（Errors 命名空间），未收录的错误码或非结构化错误退回原始信息
+export function formatError(error: unknown, t?: Translator): string {
+  if (!isMurmurError(error)) {
+    return error instanceof Error ? error.message : String(error)
+  }
+  if (t && t.has(error.code)) {
+    const message = t(error.code, error.params)
+    return error.detail ? `${message}\n${error.detail}` : message
+  }
+  return error.detail ?? error.code
+}
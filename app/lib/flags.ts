@@ -0,0 +1,39 @@
+// 转写段落标记的原因：{ code, ...params }，按原因码本地化（Flags 命名空间）
+export type FlagWord = {
+  text: string
+  probability: number
+}
+
+export type FlagReason = { code: string; [param: string]: unknown }
+
+export type SegmentFlag = {
+  kind: string
+  reason: FlagReason
+}
+
+type Translator = {
+  (key: string, values?: Record<string, string | number>): string
+  has: (key: string) => boolean
+}
+
+// 参数转为文案占位符的取值，词列表显示为 "词(0.42)"
+function formatParams(reason: FlagReason): Record<string, string | number> {
+  const values: Record<string, string | number> = {}
+  for (const [name, value] of Object.entries(reason)) {
+    if (name === 'code') continue
+    if (Array.isArray(value)) {
+      values[name] = (value as FlagWord[]).map((w) => `${w.text}(${w.probability.toFixed(2)})`).join(' ')
+    } else if (typeof value === 'number') {
+      values[name] = Number.isInteger(value) ? value : value.toFixed(1)
+    } else {
+      values[name] = String(value)
+    }
+  }
+  return values
+}
+
+// 未收录的原因码退回原因码本身；带 unit 的原因（语速）按单位选择文案
+export function formatFlagReason(reason: FlagReason, t: Translator): string {
+  const key = typeof reason.unit === 'string' ? `${reason.code}_${reason.unit}` : reason.code
+  return t.has(key) ? t(key, formatParams(reason)) : reason.code
+}
@@ -0,0 +1,536 @@
+// WAV 读取与能量分析：只依赖 RIFF 头，不额外引入解码库
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+// 音频检查与读取失败的原因，前端按 code 显示对应的提示
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AudioError {
+    // 打开或读取文件失败，保存系统错误原文
+    Io(String),
+    NotWav,
+    Malformed,
+    UnsupportedFormat { sample_rate: u32, channels: u16, bits_per_sample: u16 },
+    Empty,
+    Silent,
+}
+
+impl AudioError {
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            AudioError::Io(_) => "audio_read_failed",
+            AudioError::NotWav => "audio_not_wav",
+            AudioError::Malformed => "audio_malformed",
+            AudioError::UnsupportedFormat { .. } => "audio_unsupported_format",
+            AudioError::Empty => "audio_empty",
+            AudioError::Silent => "audio_silent",
+        }
+    }
+
+    fn unsupported(info: &WavInfo) -> Self {
+        AudioError::UnsupportedFormat {
+            sample_rate: info.sample_rate,
+            channels: info.channels,
+            bits_per_sample: info.bits_per_sample,
+        }
+    }
+}
+
+impl From<std::io::Error> for AudioError {
+    fn from(e: std::io::Error) -> Self {
+        AudioError::Io(e.to_string())
+    }
+}
+
+// 仅用于日志
+impl fmt::Display for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioError::Io(detail) => write!(f, "读取 WAV 文件失败: {}", detail),
+            AudioError::NotWav => write!(f, "不是有效的 WAV 文件"),
+            AudioError::Malformed => write!(f, "WAV 文件缺少 fmt 或 data 块"),
+            AudioError::UnsupportedFormat { sample_rate, channels, bits_per_sample } => {
+                write!(f, "音频格式不符合要求: {} Hz, {} 声道, {} 位", sample_rate, channels, bits_per_sample)
+            }
+            AudioError::Empty => write!(f, "音频不包含任何数据"),
+            AudioError::Silent => write!(f, "音频为静音"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WavInfo {
+    pub(crate) audio_format: u16,
+    pub(crate) channels: u16,
+    pub(crate) sample_rate: u32,
+    pub(crate) bits_per_sample: u16,
+    pub(crate) data_offset: u64,
+    pub(crate) data_len: u64,
+}
+
+impl WavInfo {
+    pub(crate) fn duration_ms(&self) -> u64 {
+        let frame_bytes = self.channels as u64 * (self.bits_per_sample as u64 / 8);
+        if frame_bytes == 0 || self.sample_rate == 0 {
+            return 0;
+        }
+        self.data_len / frame_bytes * 1000 / self.sample_rate as u64
+    }
+
+    // whisper.cpp 可直接读取、无需再转换的格式：16 kHz、16 位 PCM
+    pub(crate) fn is_whisper_ready(&self, channels: u16) -> bool {
+        self.audio_format == 1 && self.sample_rate == 16000 && self.bits_per_sample == 16 && self.channels == channels
+    }
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([buf[pos], buf[pos + 1]])
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]])
+}
+
+// 解析 RIFF/WAVE 头，定位 fmt 与 data 块
+pub(crate) fn read_wav_info(path: &Path) -> Result<WavInfo, AudioError> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+    let mut header = [0u8; 12];
+    file.read_exact(&mut header).map_err(|_| AudioError::NotWav)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return Err(AudioError::NotWav);
+    }
+
+    let mut fmt: Option<(u16, u16, u32, u16)> = None;
+    let mut pos = 12u64;
+    loop {
+        let mut chunk = [0u8; 8];
+        if file.read_exact(&mut chunk).is_err() {
+            return Err(AudioError::Malformed);
+        }
+        let size = read_u32(&chunk, 4) as u64;
+        pos += 8;
+        match &chunk[0..4] {
+            b"fmt " => {
+                let mut body = vec![0u8; size.min(64) as usize];
+                file.read_exact(&mut body).map_err(|_| AudioError::Malformed)?;
+                if body.len() < 16 {
+                    return Err(AudioError::Malformed);
+                }
+                let mut audio_format = read_u16(&body, 0);
+                // WAVE_FORMAT_EXTENSIBLE：实际格式位于子格式 GUID 的前两个字节
+                if audio_format == 0xFFFE && body.len() >= 26 {
+                    audio_format = read_u16(&body, 24);
+                }
+                fmt = Some((audio_format, read_u16(&body, 2), read_u32(&body, 4), read_u16(&body, 14)));
+            }
+            b"data" => {
+                let (audio_format, channels, sample_rate, bits_per_sample) =
+                    fmt.ok_or(AudioError::Malformed)?;
+                // 流式写出的 WAV 可能没有回填 data 长度，按文件剩余长度计算
+                let remaining = file_len.saturating_sub(pos);
+                let data_len = if size == 0 || size == 0xFFFF_FFFF || size > remaining { remaining } else { size };
+                return Ok(WavInfo {
+                    audio_format,
+                    channels,
+                    sample_rate,
+                    bits_per_sample,
+                    data_offset: pos,
+                    data_len,
+                });
+            }
+            _ => {}
+        }
+        // 块按偶数字节对齐
+        pos += size + (size & 1);
+        file.seek(SeekFrom::Start(pos))?;
+    }
+}
+
+// 约 -60 dBFS，低于该幅度的样本视为静音
+const SILENCE_AMPLITUDE: i16 = 32;
+
+// 检查待识别的音频：格式正确、包含数据且不是全静音，避免把空的转换结果交给 whisper
+pub(crate) fn validate_for_whisper(path: &Path, channels: u16) -> Result<WavInfo, AudioError> {
+    let info = read_wav_info(path)?;
+    if !info.is_whisper_ready(channels) {
+        return Err(AudioError::unsupported(&info));
+    }
+    if info.duration_ms() == 0 {
+        return Err(AudioError::Empty);
+    }
+    // 遇到第一个非静音样本即返回，正常音频无需读完整个文件
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(info.data_offset))?;
+    let mut reader = BufReader::new(file.take(info.data_len));
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = read_full(&mut reader, &mut buf)?;
+        if n == 0 {
+            return Err(AudioError::Silent);
+        }
+        let loud = buf[..n - n % 2]
+            .chunks_exact(2)
+            .any(|b| i16::from_le_bytes([b[0], b[1]]).saturating_abs() > SILENCE_AMPLITUDE);
+        if loud {
+            return Ok(info);
+        }
+    }
+}
+
+// 以固定窗口计算 RMS（0.0-1.0），多声道取平均
+#[derive(Debug, Clone)]
+pub(crate) struct EnergyProfile {
+    pub(crate) window_ms: u32,
+    pub(crate) rms: Vec<f32>,
+}
+
+impl EnergyProfile {
+    fn window_range(&self, start_ms: i64, end_ms: i64) -> std::ops::Range<usize> {
+        let w = self.window_ms.max(1) as i64;
+        let first = (start_ms.max(0) / w) as usize;
+        let last = ((end_ms.max(0) + w - 1) / w) as usize;
+        first.min(self.rms.len())..last.min(self.rms.len())
+    }
+
+    // 区间内最大窗口能量（dBFS），区间超出音频范围时返回 None
+    pub(crate) fn max_db(&self, start_ms: i64, end_ms: i64) -> Option<f32> {
+        let range = self.window_range(start_ms, end_ms);
+        self.rms[range].iter().cloned().fold(None, |acc: Option<f32>, v| {
+            Some(acc.map_or(v, |a| a.max(v)))
+        }).map(rms_to_db)
+    }
+}
+
+pub(crate) fn rms_to_db(rms: f32) -> f32 {
+    if rms <= 1e-10 {
+        -100.0
+    } else {
+        20.0 * rms.log10()
+    }
+}
+
+pub(crate) fn compute_energy(path: &Path, window_ms: u32) -> Result<EnergyProfile, AudioError> {
+    let info = read_wav_info(path)?;
+    if info.audio_format != 1 || info.bits_per_sample != 16 {
+        return Err(AudioError::unsupported(&info));
+    }
+    let channels = info.channels.max(1) as usize;
+    let frames_per_window = (info.sample_rate as u64 * window_ms.max(1) as u64 / 1000).max(1) as usize;
+
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(info.data_offset))?;
+    let mut reader = BufReader::new(file.take(info.data_len));
+
+    let frame_bytes = channels * 2;
+    let mut buf = vec![0u8; frame_bytes * frames_per_window];
+    let mut rms = Vec::new();
+    loop {
+        let n = read_full(&mut reader, &mut buf)?;
+        let frames = n / frame_bytes;
+        if frames == 0 {
+            break;
+        }
+        let mut sum = 0f64;
+        for f in 0..frames {
+            let mut mixed = 0f64;
+            for c in 0..channels {
+                let p = f * frame_bytes + c * 2;
+                mixed += i16::from_le_bytes([buf[p], buf[p + 1]]) as f64;
+            }
+            let v = mixed / channels as f64 / 32768.0;
+            sum += v * v;
+        }
+        rms.push((sum / frames as f64).sqrt() as f32);
+        if n < buf.len() {
+            break;
+        }
+    }
+
+    Ok(EnergyProfile { window_ms, rms })
+}
+
+// 按能量阈值检测语音区间（毫秒），合并间隔小于 min_silence_ms 的区间，
+// 丢弃短于 min_speech_ms 的区间，超过 max_speech_ms 的区间等分切开
+pub(crate) fn speech_regions(
+    energy: &EnergyProfile,
+    threshold_db: f32,
+    min_speech_ms: i64,
+    min_silence_ms: i64,
+    max_speech_ms: i64,
+) -> Vec<(i64, i64)> {
+    let w = energy.window_ms.max(1) as i64;
+    let mut raw: Vec<(i64, i64)> = Vec::new();
+    for (i, rms) in energy.rms.iter().enumerate() {
+        if rms_to_db(*rms) < threshold_db {
+            continue;
+        }
+        let (start, end) = (i as i64 * w, (i as i64 + 1) * w);
+        match raw.last_mut() {
+            Some(last) if start - last.1 < min_silence_ms => last.1 = end,
+            _ => raw.push((start, end)),
+        }
+    }
+
+    let mut regions = Vec::new();
+    for (start, end) in raw.into_iter().filter(|(s, e)| e - s >= min_speech_ms) {
+        let parts = if max_speech_ms > 0 { ((end - start) + max_speech_ms - 1) / max_speech_ms } else { 1 };
+        let len = (end - start) / parts.max(1);
+        for k in 0..parts.max(1) {
+            let s = start + k * len;
+            let e = if k == parts.max(1) - 1 { end } else { s + len };
+            regions.push((s, e));
+        }
+    }
+    regions
+}
+
+// 读取区间内的采样（多声道取平均，范围 -1.0~1.0），仅支持 16-bit PCM
+pub(crate) fn read_mono_samples(path: &Path, start_ms: i64, end_ms: i64) -> Result<Vec<f32>, AudioError> {
+    let info = read_wav_info(path)?;
+    if info.audio_format != 1 || info.bits_per_sample != 16 {
+        return Err(AudioError::unsupported(&info));
+    }
+    let channels = info.channels.max(1) as u64;
+    let frame_bytes = channels * 2;
+    let to_frame = |ms: i64| ms.max(0) as u64 * info.sample_rate as u64 / 1000;
+    let total_frames = info.data_len / frame_bytes;
+    let first = to_frame(start_ms).min(total_frames);
+    let last = to_frame(end_ms).min(total_frames);
+
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(info.data_offset + first * frame_bytes))?;
+    let mut buf = vec![0u8; ((last - first) * frame_bytes) as usize];
+    let n = read_full(&mut BufReader::new(file), &mut buf)?;
+    Ok(buf[..n]
+        .chunks_exact(frame_bytes as usize)
+        .map(|frame| {
+            let sum: f32 = frame
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32)
+                .sum();
+            sum / channels as f32 / 32768.0
+        })
+        .collect())
+}
+
+// 时间轴概览使用的能量 VAD 参数
+const OVERVIEW_SPEECH_DB: f32 = -40.0;
+const OVERVIEW_MIN_SPEECH_MS: i64 = 250;
+const OVERVIEW_MIN_SILENCE_MS: i64 = 300;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct SpeechRegion {
+    pub(crate) start_ms: i64,
+    pub(crate) end_ms: i64,
+}
+
+// 波形时间轴所需的数据：peaks 为每个像素列的 [最小值, 最大值]，rms 为每个窗口的响度（dBFS）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct AudioOverview {
+    pub(crate) duration_ms: u64,
+    pub(crate) peaks: Vec<[f32; 2]>,
+    pub(crate) window_ms: u32,
+    pub(crate) rms_db: Vec<f32>,
+    pub(crate) speech: Vec<SpeechRegion>,
+}
+
+pub(crate) fn compute_overview(path: &Path, points: usize, window_ms: u32) -> Result<AudioOverview, AudioError> {
+    let info = read_wav_info(path)?;
+    if info.audio_format != 1 || info.bits_per_sample != 16 {
+        return Err(AudioError::unsupported(&info));
+    }
+    let channels = info.channels.max(1) as usize;
+    let frame_bytes = channels * 2;
+    let total_frames = (info.data_len / frame_bytes as u64) as usize;
+    let points = points.clamp(1, total_frames.max(1));
+    let per_point = total_frames.div_ceil(points).max(1);
+
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(info.data_offset))?;
+    let mut reader = BufReader::new(file.take(info.data_len));
+    let mut buf = vec![0u8; frame_bytes * per_point];
+    let mut peaks = Vec::with_capacity(points);
+    loop {
+        let n = read_full(&mut reader, &mut buf)?;
+        let frames = n / frame_bytes;
+        if frames == 0 {
+            break;
+        }
+        let (mut lo, mut hi) = (0f32, 0f32);
+        for frame in buf[..frames * frame_bytes].chunks_exact(frame_bytes) {
+            let sum: f32 = frame.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]]) as f32).sum();
+            let v = sum / channels as f32 / 32768.0;
+            lo = lo.min(v);
+            hi = hi.max(v);
+        }
+        peaks.push([lo, hi]);
+        if n < buf.len() {
+            break;
+        }
+    }
+
+    let energy = compute_energy(path, window_ms)?;
+    let speech = speech_regions(&energy, OVERVIEW_SPEECH_DB, OVERVIEW_MIN_SPEECH_MS, OVERVIEW_MIN_SILENCE_MS, 0)
+        .into_iter()
+        .map(|(start_ms, end_ms)| SpeechRegion { start_ms, end_ms })
+        .collect();
+    Ok(AudioOverview {
+        duration_ms: info.duration_ms(),
+        peaks,
+        window_ms: energy.window_ms,
+        rms_db: energy.rms.iter().map(|r| rms_to_db(*r)).collect(),
+        speech,
+    })
+}
+
+// 概览按参数缓存在音频旁：<音频路径>.overview-<点数>-<窗口>.json，音频更新后自动失效
+pub(crate) fn load_or_compute_overview(path: &Path, points: usize, window_ms: u32) -> Result<AudioOverview, AudioError> {
+    let cache_path = PathBuf::from(format!("{}.overview-{}-{}.json", path.display(), points, window_ms));
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    if let (Some(cache_time), Some(audio_time)) = (modified(&cache_path), modified(path)) {
+        if cache_time >= audio_time {
+            if let Some(overview) = std::fs::read_to_string(&cache_path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+            {
+                return Ok(overview);
+            }
+        }
+    }
+    let overview = compute_overview(path, points, window_ms)?;
+    // 缓存写入失败不影响返回结果
+    if let Ok(json) = serde_json::to_string(&overview) {
+        let _ = std::fs::write(&cache_path, json);
+    }
+    Ok(overview)
+}
+
+// 尽量读满缓冲区，返回实际读取的字节数
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, AudioError> {
+    let mut total = 0;
+    while total < buf.len() {
+        match reader.read(&mut buf[total..]) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+pub(crate) fn write_test_wav(path: &Path, sample_rate: u32, channels: u16, samples: &[i16]) {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&channels.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+    bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for s in samples {
+        bytes.extend_from_slice(&s.to_le_bytes());
+    }
+    std::fs::write(path, bytes).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_wav_info_and_energy() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.wav");
+        // 前 0.5 秒静音，后 0.5 秒方波
+        let mut samples = vec![0i16; 8000];
+        samples.extend((0..8000).map(|i| if i % 2 == 0 { 16384 } else { -16384 }));
+        write_test_wav(&path, 16000, 1, &samples);
+
+        let info = read_wav_info(&path).unwrap();
+        assert_eq!(info.sample_rate, 16000);
+        assert_eq!(info.channels, 1);
+        assert_eq!(info.duration_ms(), 1000);
+
+        let energy = compute_energy(&path, 100).unwrap();
+        assert_eq!(energy.rms.len(), 10);
+        assert!(energy.max_db(0, 500).unwrap() < -90.0);
+        assert!(energy.max_db(500, 1000).unwrap() > -7.0);
+    }
+
+    #[test]
+    fn test_speech_regions_and_samples() {
+        let energy = EnergyProfile { window_ms: 100, rms: vec![0.0, 0.3, 0.3, 0.0, 0.3, 0.0, 0.0, 0.0, 0.3, 0.0] };
+        // 第 3 个窗口的短暂停顿被合并，最后一个孤立窗口短于 200ms 被丢弃
+        assert_eq!(speech_regions(&energy, -30.0, 200, 150, 0), vec![(100, 500)]);
+        assert_eq!(speech_regions(&energy, -30.0, 200, 150, 250), vec![(100, 300), (300, 500)]);
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.wav");
+        // 立体声：左声道 16384，右声道 0
+        let samples: Vec<i16> = (0..1600).flat_map(|_| [16384, 0]).collect();
+        write_test_wav(&path, 16000, 2, &samples);
+        let mono = read_mono_samples(&path, 50, 150).unwrap();
+        assert_eq!(mono.len(), 1600 - 800);
+        assert!((mono[0] - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_overview_and_cache() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.wav");
+        // 0.5 秒静音 + 0.5 秒方波
+        let mut samples = vec![0i16; 8000];
+        samples.extend((0..8000).map(|i| if i % 2 == 0 { 16384 } else { -16384 }));
+        write_test_wav(&path, 16000, 1, &samples);
+
+        let overview = load_or_compute_overview(&path, 4, 100).unwrap();
+        assert_eq!(overview.duration_ms, 1000);
+        assert_eq!(overview.peaks, vec![[0.0, 0.0], [0.0, 0.0], [-0.5, 0.5], [-0.5, 0.5]]);
+        assert_eq!(overview.rms_db.len(), 10);
+        assert_eq!(overview.speech, vec![SpeechRegion { start_ms: 500, end_ms: 1000 }]);
+        assert!(dir.path().join("a.wav.overview-4-100.json").exists());
+        assert_eq!(load_or_compute_overview(&path, 4, 100).unwrap().peaks.len(), 4);
+    }
+
+    #[test]
+    fn test_validate_for_whisper() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.wav");
+        let mut samples = vec![0i16; 16000];
+        samples[12000] = 1000;
+        write_test_wav(&path, 16000, 1, &samples);
+        assert_eq!(validate_for_whisper(&path, 1).unwrap().duration_ms(), 1000);
+        // 声道数与要求不符
+        assert_eq!(
+            validate_for_whisper(&path, 2).unwrap_err(),
+            AudioError::UnsupportedFormat { sample_rate: 16000, channels: 1, bits_per_sample: 16 }
+        );
+
+        write_test_wav(&path, 16000, 1, &[0i16; 16000]);
+        assert_eq!(validate_for_whisper(&path, 1).unwrap_err(), AudioError::Silent);
+        write_test_wav(&path, 16000, 1, &[]);
+        assert_eq!(validate_for_whisper(&path, 1).unwrap_err().code(), "audio_empty");
+        write_test_wav(&path, 44100, 1, &[1000i16; 100]);
+        assert!(!read_wav_info(&path).unwrap().is_whisper_ready(1));
+    }
+
+    #[test]
+    fn test_rejects_non_wav() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.wav");
+        std::fs::write(&path, b"not a wav file at all").unwrap();
+        assert_eq!(read_wav_info(&path).unwrap_err(), AudioError::NotWav);
+    }
+}
@@ -0,0 +1,256 @@
+// 内容寻址的结果缓存：按输入媒体的哈希与生效设置计算键，命中时直接复用转换后的音频与转写结果。
+// 目录结构：<应用数据>/cache/<键>/entry.json + 缓存文件
+use crate::calculate_directory_size;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub(crate) const CACHE_DIR: &str = "cache";
+const ENTRY_FILE: &str = "entry.json";
+// 键的版本号，缓存文件格式变化时递增，使旧缓存自然失效
+const KEY_VERSION: &str = "v1";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct CacheEntry {
+    pub(crate) key: String,
+    // audio：转换后的 wav；result：转写结果与导出文件
+    pub(crate) kind: String,
+    pub(crate) source: String,
+    pub(crate) created_at: u64,
+    pub(crate) last_used_at: u64,
+    pub(crate) files: Vec<String>,
+    #[serde(default)]
+    pub(crate) size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub(crate) struct EvictReport {
+    pub(crate) removed: Vec<String>,
+    pub(crate) freed_bytes: u64,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn hash_bytes(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+// 分块读取，避免把大视频整个读入内存
+pub(crate) fn hash_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+// 由若干部分组合出缓存键，各部分之间用 \0 分隔
+pub(crate) fn key(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_VERSION.as_bytes());
+    for part in parts {
+        hasher.update([0u8]);
+        hasher.update(part.as_bytes());
+    }
+    to_hex(&hasher.finalize())
+}
+
+// 对象键排序后的紧凑 JSON，保证同样的设置总是得到同样的字符串
+pub(crate) fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", Value::String(k.clone()), canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+pub(crate) struct ResultCache {
+    root: PathBuf,
+}
+
+impl ResultCache {
+    pub(crate) fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn entry_dir(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    fn read_entry(dir: &Path) -> Option<CacheEntry> {
+        let content = std::fs::read_to_string(dir.join(ENTRY_FILE)).ok()?;
+        let mut entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        entry.size_bytes = calculate_directory_size(dir);
+        Some(entry)
+    }
+
+    fn write_entry(dir: &Path, entry: &CacheEntry) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(entry).map_err(|e| format!("序列化缓存信息失败: {}", e))?;
+        std::fs::write(dir.join(ENTRY_FILE), json).map_err(|e| format!("写入缓存信息失败: {}", e))
+    }
+
+    // 命中时返回缓存目录与记录，并更新最近使用时间；文件缺失的记录视为未命中
+    pub(crate) fn lookup(&self, key: &str, now: u64) -> Option<(PathBuf, CacheEntry)> {
+        let dir = self.entry_dir(key);
+        let mut entry = Self::read_entry(&dir)?;
+        if !entry.files.iter().all(|f| dir.join(f).is_file()) {
+            return None;
+        }
+        entry.last_used_at = now;
+        let _ = Self::write_entry(&dir, &entry);
+        Some((dir, entry))
+    }
+
+    // files 为 (源文件, 缓存内文件名)；先写入临时目录再整体改名，避免留下不完整的缓存
+    pub(crate) fn store(
+        &self,
+        key: &str,
+        kind: &str,
+        source: &str,
+        files: &[(PathBuf, String)],
+        now: u64,
+    ) -> Result<(), String> {
+        std::fs::create_dir_all(&self.root).map_err(|e| format!("创建缓存目录失败: {}", e))?;
+        let staging = self.root.join(format!(".tmp-{}-{}", key, std::process::id()));
+        let _ = std::fs::remove_dir_all(&staging);
+        std::fs::create_dir_all(&staging).map_err(|e| format!("创建缓存目录失败: {}", e))?;
+        let result = (|| {
+            for (from, name) in files {
+                // 复制而不是硬链接：任务中的文件之后可能被改写（例如重命名说话人）
+                std::fs::copy(from, staging.join(name)).map_err(|e| format!("写入缓存失败: {}: {}", name, e))?;
+            }
+            let entry = CacheEntry {
+                key: key.to_string(),
+                kind: kind.to_string(),
+                source: source.to_string(),
+                created_at: now,
+                last_used_at: now,
+                files: files.iter().map(|(_, name)| name.clone()).collect(),
+                size_bytes: 0,
+            };
+            Self::write_entry(&staging, &entry)?;
+            let dir = self.entry_dir(key);
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::rename(&staging, &dir).map_err(|e| format!("写入缓存失败: {}", e))
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_dir_all(&staging);
+        }
+        result
+    }
+
+    // 把缓存文件放到目标位置，targets 为 (缓存内文件名, 目标路径)
+    pub(crate) fn restore(dir: &Path, targets: &[(String, PathBuf)]) -> Result<(), String> {
+        for (name, to) in targets {
+            std::fs::copy(dir.join(name), to).map_err(|e| format!("恢复缓存失败: {}: {}", name, e))?;
+        }
+        Ok(())
+    }
+
+    // 按最近使用时间从新到旧排列
+    pub(crate) fn list(&self) -> Vec<CacheEntry> {
+        let mut entries: Vec<CacheEntry> = std::fs::read_dir(&self.root)
+            .map(|entries| entries.flatten().filter_map(|e| Self::read_entry(&e.path())).collect())
+            .unwrap_or_default();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.last_used_at));
+        entries
+    }
+
+    // keys 为 None 时清空全部缓存
+    pub(crate) fn evict(&self, keys: Option<&[String]>) -> EvictReport {
+        let mut report = EvictReport::default();
+        for entry in self.list() {
+            if keys.is_some_and(|keys| !keys.contains(&entry.key)) {
+                continue;
+            }
+            if std::fs::remove_dir_all(self.entry_dir(&entry.key)).is_ok() {
+                report.freed_bytes += entry.size_bytes;
+                report.removed.push(entry.key);
+            }
+        }
+        report
+    }
+
+    // 超出容量时按最近最少使用淘汰，keep 指定的记录（刚写入的）不淘汰
+    pub(crate) fn enforce_limit(&self, max_bytes: u64, keep: &str) -> EvictReport {
+        let entries = self.list();
+        let mut total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+        let mut victims = Vec::new();
+        for entry in entries.iter().rev() {
+            if total <= max_bytes {
+                break;
+            }
+            if entry.key != keep {
+                total -= entry.size_bytes;
+                victims.push(entry.key.clone());
+            }
+        }
+        if victims.is_empty() {
+            return EvictReport::default();
+        }
+        self.evict(Some(&victims))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_canonical_json_and_key() {
+        let a: Value = serde_json::from_str(r#"{"b":1,"a":{"y":[1,2],"x":"s"}}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":{"x":"s","y":[1,2]},"b":1}"#).unwrap();
+        assert_eq!(canonical_json(&a), r#"{"a":{"x":"s","y":[1,2]},"b":1}"#);
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+        assert_ne!(key(&["ab", "c"]), key(&["a", "bc"]));
+        assert_eq!(hash_bytes(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_store_lookup_evict() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("a.wav.srt");
+        std::fs::write(&src, vec![1u8; 100]).unwrap();
+        let cache = ResultCache::new(temp.path().join("cache"));
+
+        assert!(cache.lookup("k1", 1).is_none());
+        cache.store("k1", "result", "a.mp3", &[(src.clone(), "srt".to_string())], 1).unwrap();
+        cache.store("k2", "result", "b.mp3", &[(src.clone(), "srt".to_string())], 2).unwrap();
+        let (dir, entry) = cache.lookup("k1", 3).unwrap();
+        assert_eq!(entry.source, "a.mp3");
+
+        let restored = temp.path().join("b.wav.srt");
+        ResultCache::restore(&dir, &[("srt".to_string(), restored.clone())]).unwrap();
+        assert_eq!(std::fs::read(&restored).unwrap().len(), 100);
+
+        // k1 刚被使用，容量不足时先淘汰 k2
+        let one_entry = cache.list()[0].size_bytes;
+        let report = cache.enforce_limit(one_entry, "k1");
+        assert_eq!(report.removed, vec!["k2".to_string()]);
+        let report = cache.evict(None);
+        assert_eq!(report.removed, vec!["k1".to_string()]);
+        assert!(cache.list().is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+// 配置导入导出：把设置与配置方案（含自定义填充词、脱敏规则）打包为一个可移植的 JSON 文件。
+// 本机路径在导出时尽量改写为 ${APP_DATA} / ${HOME} 开头的相对写法，无法改写的记录下来提示导入方重新设置；
+// 另外支持一个由管理员放置的托管配置文件，其中的值覆盖用户设置
+use crate::profiles::{Profile, ProfileStore};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+pub(crate) const BUNDLE_FORMAT: &str = "murmur-config";
+pub(crate) const BUNDLE_VERSION: u32 = 1;
+
+// 指定托管配置文件的环境变量；未设置时使用系统级的默认位置
+pub(crate) const MANAGED_CONFIG_ENV: &str = "MURMUR_MANAGED_CONFIG";
+
+// 含本机路径的设置字段，嵌套字段按层级列出
+const PATH_FIELDS: &[&[&str]] = &[
+    &["whisper_models_path"],
+    &["temp_dir_path"],
+    &["speaker_model_path"],
+    &["vad", "model_path"],
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct Bundle {
+    pub(crate) format: String,
+    pub(crate) version: u32,
+    #[serde(default)]
+    pub(crate) exported_at: String,
+    pub(crate) settings: Map<String, Value>,
+    #[serde(default)]
+    pub(crate) profiles: Vec<Profile>,
+    // 导出时无法改写为相对路径的字段
+    #[serde(default)]
+    pub(crate) machine_paths: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ImportMode {
+    // 导入的值覆盖同名字段与同名方案，本地其余内容保留
+    Merge,
+    // 以默认设置为基础，只保留导入的内容
+    Replace,
+}
+
+// 本地与导入文件取值不同的字段
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct Conflict {
+    pub(crate) field: String,
+    pub(crate) local: Value,
+    pub(crate) incoming: Value,
+}
+
+fn field_mut<'a>(map: &'a mut Map<String, Value>, path: &[&str]) -> Option<&'a mut Value> {
+    let (last, parents) = path.split_last()?;
+    let mut current = map;
+    for key in parents {
+        current = current.get_mut(*key)?.as_object_mut()?;
+    }
+    current.get_mut(*last)
+}
+
+fn to_portable(path: &Path, bases: &[(&str, PathBuf)]) -> Option<String> {
+    bases.iter().find_map(|(token, base)| {
+        let rest = path.strip_prefix(base).ok()?;
+        let mut portable = format!("${{{}}}", token);
+        for part in rest.components() {
+            portable.push('/');
+            portable.push_str(&part.as_os_str().to_string_lossy());
+        }
+        Some(portable)
+    })
+}
+
+// 展开 ${TOKEN}/a/b；未知的 TOKEN 返回 None，普通路径原样返回
+fn from_portable(value: &str, bases: &[(&str, PathBuf)]) -> Option<String> {
+    let Some(rest) = value.strip_prefix("${") else {
+        return Some(value.to_string());
+    };
+    let (token, rest) = rest.split_once('}')?;
+    let (_, base) = bases.iter().find(|(t, _)| *t == token)?;
+    let path = rest.split('/').filter(|p| !p.is_empty()).fold(base.clone(), |p, part| p.join(part));
+    Some(path.to_string_lossy().to_string())
+}
+
+// 导出前改写路径字段，返回无法改写（仍是本机绝对路径）的字段名
+pub(crate) fn export_paths(settings: &mut Map<String, Value>, bases: &[(&str, PathBuf)]) -> Vec<String> {
+    let mut flagged = Vec::new();
+    for path in PATH_FIELDS {
+        if let Some(value) = field_mut(settings, path) {
+            let Some(raw) = value.as_str() else { continue };
+            match to_portable(Path::new(raw), bases) {
+                Some(portable) => *value = Value::from(portable),
+                None => flagged.push(path.join(".")),
+            }
+        }
+    }
+    flagged
+}
+
+// 导入时展开路径字段，返回需要重新设置的字段：无法识别的前缀（已清空）或本机不存在的路径
+pub(crate) fn import_paths(settings: &mut Map<String, Value>, bases: &[(&str, PathBuf)]) -> Vec<String> {
+    let mut flagged = Vec::new();
+    for path in PATH_FIELDS {
+        if let Some(value) = field_mut(settings, path) {
+            let Some(raw) = value.as_str() else { continue };
+            match from_portable(raw, bases) {
+                Some(local) => {
+                    if !Path::new(&local).exists() {
+                        flagged.push(path.join("."));
+                    }
+                    *value = Value::from(local);
+                }
+                None => {
+                    *value = Value::Null;
+                    flagged.push(path.join("."));
+                }
+            }
+        }
+    }
+    flagged
+}
+
+pub(crate) fn parse(content: &str) -> Result<Bundle, String> {
+    let bundle: Bundle = serde_json::from_str(content).map_err(|e| format!("解析配置文件失败: {}", e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(format!("未知的配置文件格式: {}", bundle.format));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!("配置文件版本 {} 高于当前支持的版本 {}", bundle.version, BUNDLE_VERSION));
+    }
+    Ok(bundle)
+}
+
+// 列出导入后会改变的顶层字段
+pub(crate) fn diff(local: &Map<String, Value>, incoming: &Map<String, Value>, ignored: &[&str]) -> Vec<Conflict> {
+    incoming
+        .iter()
+        .filter(|(key, _)| !ignored.contains(&key.as_str()))
+        .filter_map(|(key, value)| {
+            let current = local.get(key)?;
+            (current != value).then(|| Conflict { field: key.clone(), local: current.clone(), incoming: value.clone() })
+        })
+        .collect()
+}
+
+// 合并配置方案，返回被导入内容覆盖的同名方案
+pub(crate) fn merge_profiles(store: &mut ProfileStore, incoming: Vec<Profile>, mode: ImportMode) -> Vec<String> {
+    if mode == ImportMode::Replace {
+        let replaced = store.profiles.iter().map(|p| p.name.clone()).collect();
+        store.profiles = incoming;
+        if store.active.as_ref().is_some_and(|a| store.profiles.iter().all(|p| &p.name != a)) {
+            store.active = None;
+        }
+        return replaced;
+    }
+    let mut conflicts = Vec::new();
+    for profile in incoming {
+        match store.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => {
+                if existing.settings != profile.settings {
+                    conflicts.push(profile.name.clone());
+                    *existing = profile;
+                }
+            }
+            None => store.profiles.push(profile),
+        }
+    }
+    conflicts
+}
+
+// 托管配置文件的位置：环境变量优先，其次为系统级目录下的 managed.json；文件不存在时返回 None
+pub(crate) fn managed_config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(MANAGED_CONFIG_ENV).filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let path = if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/MurMur/managed.json")
+    } else if cfg!(target_os = "windows") {
+        let program_data = std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+        PathBuf::from(program_data).join("MurMur").join("managed.json")
+    } else {
+        PathBuf::from("/etc/murmur/managed.json")
+    };
+    path.is_file().then_some(path)
+}
+
+// 托管配置可以是导出的配置文件，也可以只是部分设置的 JSON 对象
+pub(crate) fn load_managed(path: &Path, bases: &[(&str, PathBuf)]) -> Result<Map<String, Value>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("读取托管配置失败: {}", e))?;
+    let value: Value = serde_json::from_str(&content).map_err(|e| format!("解析托管配置失败: {}", e))?;
+    let mut settings = match value {
+        Value::Object(map) if map.get("format").and_then(Value::as_str) == Some(BUNDLE_FORMAT) => {
+            parse(&content)?.settings
+        }
+        Value::Object(map) => map,
+        _ => return Err("托管配置必须是 JSON 对象".to_string()),
+    };
+    settings.remove("schema_version");
+    import_paths(&mut settings, bases);
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn map(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_portable_paths() {
+        let home = TempDir::new().unwrap();
+        let app_data = home.path().join("app");
+        std::fs::create_dir_all(app_data.join("models")).unwrap();
+        let bases = vec![("APP_DATA", app_data.clone()), ("HOME", home.path().to_path_buf())];
+
+        let mut settings = map(serde_json::json!({
+            "whisper_models_path": app_data.join("models").to_string_lossy(),
+            "temp_dir_path": "/elsewhere/tmp",
+            "speaker_model_path": null,
+            "vad": {"model_path": home.path().join("vad.bin").to_string_lossy()}
+        }));
+        assert_eq!(export_paths(&mut settings, &bases), vec!["temp_dir_path".to_string()]);
+        assert_eq!(settings["whisper_models_path"], "${APP_DATA}/models");
+        assert_eq!(settings["vad"]["model_path"], "${HOME}/vad.bin");
+
+        settings.insert("speaker_model_path".to_string(), Value::from("${NOWHERE}/spk.onnx"));
+        let flagged = import_paths(&mut settings, &bases);
+        assert_eq!(flagged, vec!["temp_dir_path", "speaker_model_path", "vad.model_path"]);
+        assert_eq!(settings["whisper_models_path"], app_data.join("models").to_string_lossy().as_ref());
+        assert_eq!(settings["speaker_model_path"], Value::Null);
+    }
+
+    #[test]
+    fn test_merge_and_managed() {
+        let profile = |name: &str, model: &str| Profile {
+            name: name.to_string(),
+            settings: map(serde_json::json!({ "whisper_model": model })),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let mut store = ProfileStore { active: Some("local".to_string()), profiles: vec![profile("local", "a"), profile("team", "a")] };
+        let conflicts = merge_profiles(&mut store, vec![profile("team", "b"), profile("new", "c")], ImportMode::Merge);
+        assert_eq!(conflicts, vec!["team".to_string()]);
+        assert_eq!(store.profiles.len(), 3);
+        merge_profiles(&mut store, vec![profile("team", "b")], ImportMode::Replace);
+        assert_eq!(store.active, None);
+
+        let local = map(serde_json::json!({"thread_count": 4, "whisper_model": "a", "schema_version": 1}));
+        let incoming = map(serde_json::json!({"thread_count": 4, "whisper_model": "b", "schema_version": 0}));
+        let conflicts = diff(&local, &incoming, &["schema_version"]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, "whisper_model");
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("managed.json");
+        std::fs::write(&path, r#"{"format":"murmur-config","version":1,"settings":{"schema_version":1,"whisper_model":"team"}}"#).unwrap();
+        assert_eq!(load_managed(&path, &[]).unwrap(), map(serde_json::json!({"whisper_model": "team"})));
+        std::fs::write(&path, r#"{"thread_count": 2}"#).unwrap();
+        assert_eq!(load_managed(&path, &[]).unwrap(), map(serde_json::json!({"thread_count": 2})));
+        assert!(parse(r#"{"format":"murmur-config","version":9,"settings":{}}"#).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+// 识别使用的线程数与处理器数（whisper-cli 的 --threads / --processors）。
+// thread_count 为 0 表示自动：按物理核心数与当前负载选择；指定数值时限制在逻辑核心数以内，大机器上可以超过 8
+use serde::Serialize;
+
+pub(crate) const AUTO_THREADS: u32 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct CpuInfo {
+    pub(crate) physical_cores: u32,
+    pub(crate) logical_cores: u32,
+    // 1 分钟平均负载；Windows 上不可用，为 0
+    pub(crate) load_average: f64,
+}
+
+// 当前进程可用的逻辑核心数（考虑 CPU 亲和性与容器配额）
+pub(crate) fn logical_cores() -> u32 {
+    std::thread::available_parallelism().map_or(1, |n| n.get() as u32)
+}
+
+impl CpuInfo {
+    pub(crate) fn detect() -> Self {
+        let logical_cores = logical_cores();
+        let physical_cores = sysinfo::System::new()
+            .physical_core_count()
+            .map_or(logical_cores, |n| n as u32)
+            .clamp(1, logical_cores);
+        let load_average = sysinfo::System::load_average().one;
+        Self { physical_cores, logical_cores, load_average }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct ThreadPlan {
+    // 每个处理器使用的线程数
+    pub(crate) threads: u32,
+    pub(crate) processors: u32,
+    pub(crate) auto: bool,
+}
+
+impl ThreadPlan {
+    // 写入任务日志，便于按机器调整
+    pub(crate) fn describe(&self, cpu: &CpuInfo) -> String {
+        format!(
+            "线程 {}{}，处理器 {}（物理核心 {}，逻辑核心 {}，负载 {:.2}）",
+            self.threads,
+            if self.auto { "（自动）" } else { "" },
+            self.processors,
+            cpu.physical_cores,
+            cpu.logical_cores,
+            cpu.load_average
+        )
+    }
+}
+
+pub(crate) fn plan(thread_count: u32, processor_count: u32, cpu: &CpuInfo) -> ThreadPlan {
+    let max = cpu.logical_cores.max(1);
+    let processors = processor_count.clamp(1, max);
+    if thread_count != AUTO_THREADS {
+        return ThreadPlan { threads: thread_count.clamp(1, max), processors, auto: false };
+    }
+    // 超线程对矩阵运算帮助不大，按物理核心分配；其他程序占用的核心按平均负载扣除，
+    // 负载只是估计值，至少保留一半物理核心
+    let physical = cpu.physical_cores.clamp(1, max);
+    let busy = if cpu.load_average.is_finite() { cpu.load_average.round().max(0.0) as u32 } else { 0 };
+    let idle = physical.saturating_sub(busy).max(physical / 2).max(1);
+    ThreadPlan { threads: (idle / processors).max(1), processors, auto: true }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        let big = CpuInfo { physical_cores: 32, logical_cores: 64, load_average: 0.2 };
+        assert_eq!(plan(AUTO_THREADS, 1, &big), ThreadPlan { threads: 32, processors: 1, auto: true });
+        assert_eq!(plan(AUTO_THREADS, 4, &big).threads, 8);
+        assert_eq!(plan(24, 1, &big).threads, 24);
+        assert_eq!(plan(128, 0, &big), ThreadPlan { threads: 64, processors: 1, auto: false });
+
+        // 负载高时减少线程，但不少于一半物理核心
+        let busy = CpuInfo { physical_cores: 8, logical_cores: 16, load_average: 3.0 };
+        assert_eq!(plan(AUTO_THREADS, 1, &busy).threads, 5);
+        let saturated = CpuInfo { physical_cores: 8, logical_cores: 16, load_average: 20.0 };
+        assert_eq!(plan(AUTO_THREADS, 1, &saturated).threads, 4);
+        let tiny = CpuInfo { physical_cores: 1, logical_cores: 1, load_average: f64::NAN };
+        assert_eq!(plan(AUTO_THREADS, 2, &tiny), ThreadPlan { threads: 1, processors: 1, auto: true });
+    }
+}
@@ -0,0 +1,395 @@
+// 说话人聚类：对每个语音片段用本地 ONNX 声纹模型（ECAPA、3D-Speaker 等导出）在 CPU 上提取向量，
+// 层次聚类后按时间重叠把说话人分配给转写段落。ONNX 推理需要启用 diarization 特性
+use crate::audio;
+use crate::transcript::Transcript;
+use std::path::Path;
+
+const SAMPLE_RATE: f32 = 16000.0;
+// Kaldi 风格 fbank：25ms 帧长、10ms 帧移、80 维梅尔滤波器
+const FRAME_LEN: usize = 400;
+const FRAME_SHIFT: usize = 160;
+const FFT_SIZE: usize = 512;
+const NUM_MEL_BINS: usize = 80;
+const LOW_FREQ: f32 = 20.0;
+const HIGH_FREQ: f32 = 8000.0;
+// 语音片段检测参数
+const SPEECH_THRESHOLD_DB: f32 = -40.0;
+const MIN_SPEECH_MS: i64 = 500;
+const MIN_SILENCE_MS: i64 = 300;
+// 片段过长时可能包含多个说话人，切分后分别提取声纹
+const MAX_SPEECH_MS: i64 = 4000;
+
+// 模型输入：多数 ECAPA/CAM++ 导出使用 [1, T, 80] 的 fbank，少数直接接收 [1, N] 的波形
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModelInput {
+    Fbank,
+    Waveform,
+}
+
+impl ModelInput {
+    pub(crate) fn from_setting(value: &str) -> Self {
+        match value {
+            "waveform" => Self::Waveform,
+            _ => Self::Fbank,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DiarizationOptions {
+    // None 表示按相似度阈值自动估计人数
+    pub(crate) num_speakers: Option<usize>,
+    pub(crate) threshold: f32,
+    pub(crate) input: ModelInput,
+}
+
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * std::f32::consts::PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+fn mel(freq: f32) -> f32 {
+    1127.0 * (1.0 + freq / 700.0).ln()
+}
+
+// 三角滤波器组，按 FFT 频点给出权重
+fn mel_banks() -> Vec<Vec<f32>> {
+    let (low, high) = (mel(LOW_FREQ), mel(HIGH_FREQ));
+    let delta = (high - low) / (NUM_MEL_BINS + 1) as f32;
+    let bin_hz = SAMPLE_RATE / FFT_SIZE as f32;
+    (0..NUM_MEL_BINS)
+        .map(|m| {
+            let (left, center, right) = (low + m as f32 * delta, low + (m + 1) as f32 * delta, low + (m + 2) as f32 * delta);
+            (0..FFT_SIZE / 2 + 1)
+                .map(|k| {
+                    let f = mel(k as f32 * bin_hz);
+                    if f > left && f < right {
+                        if f <= center { (f - left) / (center - left) } else { (right - f) / (right - center) }
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// 80 维对数梅尔 fbank，按句做均值归一化
+pub(crate) fn fbank(samples: &[f32]) -> Vec<Vec<f32>> {
+    if samples.len() < FRAME_LEN {
+        return Vec::new();
+    }
+    let banks = mel_banks();
+    // povey 窗
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| (0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LEN - 1) as f32).cos()).powf(0.85))
+        .collect();
+
+    let num_frames = 1 + (samples.len() - FRAME_LEN) / FRAME_SHIFT;
+    let mut feats = Vec::with_capacity(num_frames);
+    for f in 0..num_frames {
+        // 与 Kaldi 一致使用 16-bit 整数幅度
+        let mut frame: Vec<f32> = samples[f * FRAME_SHIFT..f * FRAME_SHIFT + FRAME_LEN].iter().map(|s| s * 32768.0).collect();
+        let mean = frame.iter().sum::<f32>() / FRAME_LEN as f32;
+        frame.iter_mut().for_each(|s| *s -= mean);
+        for i in (1..FRAME_LEN).rev() {
+            frame[i] -= 0.97 * frame[i - 1];
+        }
+        frame[0] -= 0.97 * frame[0];
+
+        let mut re = vec![0f32; FFT_SIZE];
+        let mut im = vec![0f32; FFT_SIZE];
+        for i in 0..FRAME_LEN {
+            re[i] = frame[i] * window[i];
+        }
+        fft(&mut re, &mut im);
+        let power: Vec<f32> = (0..FFT_SIZE / 2 + 1).map(|k| re[k] * re[k] + im[k] * im[k]).collect();
+        feats.push(
+            banks
+                .iter()
+                .map(|bank| bank.iter().zip(&power).map(|(w, p)| w * p).sum::<f32>().max(f32::EPSILON).ln())
+                .collect::<Vec<f32>>(),
+        );
+    }
+
+    for d in 0..NUM_MEL_BINS {
+        let mean = feats.iter().map(|f| f[d]).sum::<f32>() / feats.len() as f32;
+        feats.iter_mut().for_each(|f| f[d] -= mean);
+    }
+    feats
+}
+
+fn normalize(v: &[f32]) -> Vec<f32> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt().max(f32::EPSILON);
+    v.iter().map(|x| x / norm).collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// 除 i 以外最相似的活动类别，没有时相似度为 f32::MIN
+fn nearest(sim: &[Vec<f32>], active: &[bool], i: usize) -> (usize, f32) {
+    let mut best = (i, f32::MIN);
+    for (j, &s) in sim[i].iter().enumerate() {
+        if j != i && active[j] && s > best.1 {
+            best = (j, s);
+        }
+    }
+    best
+}
+
+// 平均连接的层次聚类：指定人数时合并到该人数为止，否则合并到最大相似度低于阈值；
+// 返回每个向量的类别，类别按首次出现的顺序编号。
+// 相似度矩阵在合并后按 Lance–Williams 公式原地更新：sim(k, a∪b) = (|a|·sim(k,a) + |b|·sim(k,b)) / (|a|+|b|)，
+// 并缓存每个类别最相似的类别，长录音（上千个片段）也不需要每次合并都重新计算全部类间距离
+pub(crate) fn cluster(embeddings: &[Vec<f32>], num_speakers: Option<usize>, threshold: f32) -> Vec<usize> {
+    let vectors: Vec<Vec<f32>> = embeddings.iter().map(|e| normalize(e)).collect();
+    let n = vectors.len();
+    let mut sim: Vec<Vec<f32>> = (0..n).map(|i| (0..n).map(|j| cosine(&vectors[i], &vectors[j])).collect()).collect();
+    let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut active = vec![true; n];
+    let mut best: Vec<(usize, f32)> = (0..n).map(|i| nearest(&sim, &active, i)).collect();
+    let mut count = n;
+    let target = num_speakers.map(|k| k.max(1));
+
+    while count > 1 {
+        let mut pair = (0, 0, f32::MIN);
+        for i in (0..n).filter(|&i| active[i]) {
+            if best[i].1 > pair.2 {
+                pair = (i, best[i].0, best[i].1);
+            }
+        }
+        let done = match target {
+            Some(k) => count <= k,
+            None => pair.2 < threshold,
+        };
+        if done {
+            break;
+        }
+
+        let (a, b) = (pair.0.min(pair.1), pair.0.max(pair.1));
+        let (size_a, size_b) = (members[a].len() as f32, members[b].len() as f32);
+        for k in 0..n {
+            if active[k] && k != a && k != b {
+                let s = (size_a * sim[k][a] + size_b * sim[k][b]) / (size_a + size_b);
+                sim[k][a] = s;
+                sim[a][k] = s;
+            }
+        }
+        let merged = std::mem::take(&mut members[b]);
+        members[a].extend(merged);
+        active[b] = false;
+        count -= 1;
+
+        // 原先最相似的是 a 或 b 的类别重新查找，其他类别只需与合并后的类别比较
+        best[a] = nearest(&sim, &active, a);
+        for k in 0..n {
+            if !active[k] || k == a {
+                continue;
+            }
+            if best[k].0 == a || best[k].0 == b {
+                best[k] = nearest(&sim, &active, k);
+            } else if sim[k][a] > best[k].1 {
+                best[k] = (a, sim[k][a]);
+            }
+        }
+    }
+
+    let mut labels = vec![0; n];
+    let mut clusters: Vec<Vec<usize>> = members.into_iter().filter(|m| !m.is_empty()).collect();
+    clusters.sort_by_key(|c| c.iter().min().copied());
+    for (label, members) in clusters.iter().enumerate() {
+        for &i in members {
+            labels[i] = label;
+        }
+    }
+    labels
+}
+
+// 按时间重叠最多的片段为每个转写段落指定说话人，没有重叠的段落保持不变
+pub(crate) fn assign_speakers(transcript: &mut Transcript, regions: &[(i64, i64)], labels: &[usize]) {
+    let count = labels.iter().max().map_or(0, |m| m + 1);
+    for seg in transcript.segments.iter_mut() {
+        let mut overlap = vec![0i64; count];
+        for (&(start, end), &label) in regions.iter().zip(labels) {
+            overlap[label] += (end.min(seg.end_ms) - start.max(seg.start_ms)).max(0);
+        }
+        if let Some((label, _)) = overlap.iter().enumerate().filter(|(_, o)| **o > 0).max_by_key(|(_, o)| **o) {
+            seg.speaker = Some((label + 1).to_string());
+        }
+    }
+    let ids: Vec<String> = transcript.segments.iter().filter_map(|s| s.speaker.clone()).collect();
+    for id in ids {
+        transcript.add_speaker(&id);
+    }
+}
+
+// 按模型要求构造输入：返回形状与数据
+fn model_input(samples: &[f32], input: ModelInput) -> (Vec<usize>, Vec<f32>) {
+    match input {
+        ModelInput::Fbank => {
+            let feats = fbank(samples);
+            (vec![1, feats.len(), NUM_MEL_BINS], feats.into_iter().flatten().collect())
+        }
+        ModelInput::Waveform => (vec![1, samples.len()], samples.to_vec()),
+    }
+}
+
+#[cfg(feature = "diarization")]
+struct SpeakerModel {
+    session: ort::session::Session,
+}
+
+#[cfg(feature = "diarization")]
+impl SpeakerModel {
+    fn load(path: &Path) -> Result<Self, String> {
+        let session = ort::session::Session::builder()
+            .and_then(|b| b.commit_from_file(path))
+            .map_err(|e| format!("加载声纹模型失败: {}", e))?;
+        Ok(Self { session })
+    }
+
+    fn embed(&mut self, shape: Vec<usize>, data: Vec<f32>) -> Result<Vec<f32>, String> {
+        let tensor = ort::value::Tensor::from_array((shape, data))
+            .map_err(|e| format!("构造模型输入失败: {}", e))?;
+        let outputs = self
+            .session
+            .run(ort::inputs![tensor])
+            .map_err(|e| format!("声纹模型推理失败: {}", e))?;
+        let (_, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| format!("读取声纹向量失败: {}", e))?;
+        Ok(data.to_vec())
+    }
+}
+
+// 未启用 diarization 特性时不链接 onnxruntime，加载模型直接报错
+#[cfg(not(feature = "diarization"))]
+struct SpeakerModel;
+
+#[cfg(not(feature = "diarization"))]
+impl SpeakerModel {
+    fn load(_path: &Path) -> Result<Self, String> {
+        Err("当前版本未启用说话人聚类（需要以 diarization 特性编译）".to_string())
+    }
+
+    fn embed(&mut self, _shape: Vec<usize>, _data: Vec<f32>) -> Result<Vec<f32>, String> {
+        Err("当前版本未启用说话人聚类（需要以 diarization 特性编译）".to_string())
+    }
+}
+
+// 返回识别出的说话人数
+pub(crate) fn diarize(
+    transcript: &mut Transcript,
+    audio_path: &Path,
+    model_path: &Path,
+    options: &DiarizationOptions,
+) -> Result<usize, String> {
+    let mut model = SpeakerModel::load(model_path)?;
+    let energy = audio::compute_energy(audio_path, 50).map_err(|e| e.to_string())?;
+    let regions = audio::speech_regions(&energy, SPEECH_THRESHOLD_DB, MIN_SPEECH_MS, MIN_SILENCE_MS, MAX_SPEECH_MS);
+    if regions.is_empty() {
+        return Ok(0);
+    }
+
+    let mut embeddings = Vec::with_capacity(regions.len());
+    for &(start, end) in &regions {
+        let samples = audio::read_mono_samples(audio_path, start, end).map_err(|e| e.to_string())?;
+        let (shape, data) = model_input(&samples, options.input);
+        embeddings.push(model.embed(shape, data)?);
+    }
+
+    let labels = cluster(&embeddings, options.num_speakers, options.threshold);
+    assign_speakers(transcript, &regions, &labels);
+    Ok(labels.iter().max().map_or(0, |m| m + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Segment;
+
+    #[test]
+    fn test_fft_and_fbank() {
+        // 1 kHz 正弦波：512 点 FFT 每个频点 31.25 Hz，峰值应在第 32 个频点
+        let samples: Vec<f32> = (0..16000).map(|i| (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / SAMPLE_RATE).sin() * 0.5).collect();
+        let mut re = samples[..FFT_SIZE].to_vec();
+        let mut im = vec![0f32; FFT_SIZE];
+        fft(&mut re, &mut im);
+        let peak = (0..FFT_SIZE / 2).max_by(|&a, &b| re[a].hypot(im[a]).total_cmp(&re[b].hypot(im[b]))).unwrap();
+        assert_eq!(peak, 32);
+
+        let (shape, data) = model_input(&samples, ModelInput::Fbank);
+        assert_eq!(shape, vec![1, 1 + (16000 - FRAME_LEN) / FRAME_SHIFT, NUM_MEL_BINS]);
+        assert_eq!(data.len(), shape[1] * NUM_MEL_BINS);
+    }
+
+    #[test]
+    fn test_cluster() {
+        let embeddings = vec![
+            vec![1.0, 0.1, 0.0],
+            vec![0.0, 1.0, 0.1],
+            vec![0.9, 0.2, 0.0],
+            vec![0.1, 0.9, 0.0],
+            vec![0.0, 0.1, 1.0],
+        ];
+        assert_eq!(cluster(&embeddings, None, 0.7), vec![0, 1, 0, 1, 2]);
+        assert_eq!(cluster(&embeddings, Some(2), 0.7).iter().max(), Some(&1));
+
+        // 片段较多时：三组交替出现的向量仍能正确分开
+        let embeddings: Vec<Vec<f32>> = (0..600)
+            .map(|i| {
+                let mut v = vec![0.0; 3];
+                v[i % 3] = 1.0;
+                v[(i + 1) % 3] = 0.05 * ((i / 3) % 5) as f32;
+                v
+            })
+            .collect();
+        let labels = cluster(&embeddings, None, 0.7);
+        assert_eq!(&labels[..6], &[0, 1, 2, 0, 1, 2]);
+        assert!(labels.iter().enumerate().all(|(i, &l)| l == i % 3));
+    }
+
+    #[test]
+    fn test_assign_speakers() {
+        let mut t = Transcript {
+            segments: vec![Segment::new(0, 2000, "a".to_string()), Segment::new(2000, 5000, "b".to_string()), Segment::new(9000, 9500, "c".to_string())],
+            ..Default::default()
+        };
+        assign_speakers(&mut t, &[(0, 1800), (1800, 2500), (2500, 5000)], &[0, 1, 1]);
+        let speakers: Vec<Option<&str>> = t.segments.iter().map(|s| s.speaker.as_deref()).collect();
+        assert_eq!(speakers, vec![Some("1"), Some("2"), None]);
+        t.default_speaker_names(&crate::i18n::Messages::new("zh-CN"));
+        assert_eq!(t.speakers.get("2").map(|s| s.as_str()), Some("说话人 2"));
+    }
+}
@@ -0,0 +1,254 @@
+// 口语清理（clean verbatim）：去除语气词、填充词与口吃重复，时间轴保持不变
+use crate::transcript::{is_cjk, primary_language, Transcript};
+use regex::Regex;
+
+// 语气词：汉字语气词在任意位置出现都删除；其他语言（包括日文假名、韩文）的语气词
+// 必须前后都是词边界，避免删掉 "어머니"、"음식" 这类普通词的一部分
+const ALWAYS_FILLERS: &[(&str, &[&str])] = &[
+    ("zh", &["嗯嗯", "呃呃", "嗯", "呃", "唔"]),
+    ("en", &["um", "umm", "uh", "uhm", "uhh", "erm", "er", "hmm", "mm"]),
+    ("ja", &["えーと", "えっと", "えー", "あのー"]),
+    ("ko", &["음", "어"]),
+];
+
+// 仅当被标点单独隔开时才删除，避免误删 "那个人"、"I like it" 之类的正常用法
+const DELIMITED_FILLERS: &[(&str, &[&str])] = &[
+    ("zh", &["那个", "这个", "就是", "就是说", "然后", "啊", "额", "哎", "对吧", "那個", "這個", "就是說", "然後", "對吧"]),
+    ("en", &["you know", "i mean", "like", "so", "well", "basically", "actually", "right"]),
+    ("ja", &["あの", "その", "まあ", "なんか"]),
+    ("ko", &["그", "저", "뭐", "그러니까"]),
+];
+
+// 英文中常见的合法重复，不视为口吃
+const LEGIT_WORD_REPEATS: &[&str] = &["that", "had", "is", "do"];
+
+const CLAUSE_DELIMITERS: &[char] = &['，', ',', '、', '。', '.', '！', '!', '？', '?', '；', ';', '：', ':', '…'];
+
+fn lists_for<'a>(table: &'a [(&'a str, &'a [&'a str])], language: Option<&str>) -> Vec<&'a str> {
+    let lang = language.map(primary_language);
+    let known = lang.as_deref().filter(|l| table.iter().any(|(k, _)| k == l));
+    table
+        .iter()
+        .filter(|(k, _)| known.is_none_or(|l| *k == l))
+        .flat_map(|(_, list)| list.iter().cloned())
+        .collect()
+}
+
+// 汉字（不含假名与谚文）
+fn is_han(c: char) -> bool {
+    matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF)
+}
+
+pub(crate) struct DisfluencyFilter {
+    cjk_fillers: Option<Regex>,
+    latin_fillers: Option<Regex>,
+    delimited: Vec<String>,
+}
+
+impl DisfluencyFilter {
+    // extra_words 为用户自定义的语气词，与内置的 "任意位置删除" 词表合并
+    pub(crate) fn new(language: Option<&str>, extra_words: &[String]) -> Self {
+        let mut always: Vec<String> = lists_for(ALWAYS_FILLERS, language).iter().map(|s| s.to_string()).collect();
+        always.extend(extra_words.iter().map(|w| w.trim().to_string()).filter(|w| !w.is_empty()));
+        // 长词优先，避免 "嗯嗯" 只删掉一半
+        always.sort_by_key(|w| std::cmp::Reverse(w.chars().count()));
+
+        let (cjk, latin): (Vec<String>, Vec<String>) = always.into_iter().partition(|w| w.chars().all(is_han));
+        let join = |words: &[String]| words.iter().map(|w| regex::escape(w)).collect::<Vec<_>>().join("|");
+        let cjk_fillers = if cjk.is_empty() {
+            None
+        } else {
+            Regex::new(&format!("(?:{})[，,、]?", join(&cjk))).ok()
+        };
+        let latin_fillers = if latin.is_empty() {
+            None
+        } else {
+            Regex::new(&format!(r"(?i)\b(?:{})\b[,，]?\s*", join(&latin))).ok()
+        };
+
+        let delimited = lists_for(DELIMITED_FILLERS, language).iter().map(|s| s.to_lowercase()).collect();
+        Self { cjk_fillers, latin_fillers, delimited }
+    }
+
+    pub(crate) fn clean(&self, text: &str) -> String {
+        let mut s = self.strip_fillers(text);
+        s = self.remove_delimited(&s);
+        s = collapse_cjk_stutter(&s);
+        s = collapse_word_stutter(&s);
+        tidy(&s)
+    }
+
+    // 只删除任意位置出现的语气词
+    fn strip_fillers(&self, text: &str) -> String {
+        let mut s = text.to_string();
+        if let Some(re) = &self.cjk_fillers {
+            s = re.replace_all(&s, "").to_string();
+        }
+        if let Some(re) = &self.latin_fillers {
+            s = re.replace_all(&s, "").to_string();
+        }
+        s
+    }
+
+    // 按标点切分子句，整句恰好是填充词时连同其后的分隔符一起删除
+    fn remove_delimited(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut clause = String::new();
+        for c in text.chars() {
+            if CLAUSE_DELIMITERS.contains(&c) {
+                if self.is_delimited_filler(&clause) {
+                    clause.clear();
+                    // 句末标点保留，逗号类分隔符随填充词一起删除
+                    if matches!(c, '。' | '.' | '！' | '!' | '？' | '?') {
+                        out.push(c);
+                    }
+                } else {
+                    out.push_str(&clause);
+                    clause.clear();
+                    out.push(c);
+                }
+            } else {
+                clause.push(c);
+            }
+        }
+        if !self.is_delimited_filler(&clause) {
+            out.push_str(&clause);
+        }
+        out
+    }
+
+    fn is_delimited_filler(&self, clause: &str) -> bool {
+        let trimmed = clause.trim().to_lowercase();
+        !trimmed.is_empty() && self.delimited.contains(&trimmed)
+    }
+}
+
+// 中文口吃："我我我觉得" -> "我觉得"，"我、我觉得" -> "我觉得"；
+// 两字叠词（谢谢、看看）属于正常用法，不做处理
+fn collapse_cjk_stutter(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if is_cjk(c) {
+            let mut j = i + 1;
+            while j < chars.len() && chars[j] == c {
+                j += 1;
+            }
+            if j - i >= 3 {
+                out.push(c);
+                i = j;
+                continue;
+            }
+            // 单字 + 顿号/逗号 + 同一字
+            if j - i == 1 && i + 2 < chars.len() && matches!(chars[i + 1], '、' | '，' | ',') && chars[i + 2] == c {
+                i += 2;
+                continue;
+            }
+        }
+        out.push(c);
+        i += 1;
+    }
+    out.into_iter().collect()
+}
+
+// 英文口吃："the the cat" -> "the cat"，"I-I think" -> "I think"
+fn collapse_word_stutter(text: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for raw in text.split(' ') {
+        // 连字符口吃：I-I、w-we
+        let word = match raw.split_once('-') {
+            Some((a, b)) if !a.is_empty() && b.to_lowercase().starts_with(&a.to_lowercase()) && a.chars().count() <= 2 => b.to_string(),
+            _ => raw.to_string(),
+        };
+        if let Some(prev) = out.last() {
+            let p = prev.to_lowercase();
+            let w = word.to_lowercase();
+            if !w.is_empty() && p == w && w.chars().all(|c| c.is_alphabetic() || c == '\'') && !LEGIT_WORD_REPEATS.contains(&w.as_str()) {
+                continue;
+            }
+        }
+        out.push(word);
+    }
+    out.join(" ")
+}
+
+// 整理删除后残留的空白与标点
+fn tidy(text: &str) -> String {
+    let mut s = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    for (from, to) in [("，，", "，"), ("，。", "。"), (",,", ","), (", .", "."), (",.", "."), (" ,", ","), (" .", "."), ("、，", "，")] {
+        while s.contains(from) {
+            s = s.replace(from, to);
+        }
+    }
+    s.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '，' | ',' | '、' | '。' | '.'))
+        .trim_end_matches(|c: char| c.is_whitespace() || matches!(c, '，' | ',' | '、'))
+        .to_string()
+}
+
+// 返回清理后删除的空段数量；时间轴不变。
+// 词级结果只去掉语气词本身，单个词无法判断是否被标点隔开
+pub(crate) fn apply(transcript: &mut Transcript, filter: &DisfluencyFilter) -> usize {
+    for seg in transcript.segments.iter_mut() {
+        seg.text = filter.clean(&seg.text);
+        seg.words
+            .retain(|w| filter.strip_fillers(&w.text).chars().any(|c| c.is_alphanumeric()));
+    }
+    let before = transcript.segments.len();
+    transcript
+        .segments
+        .retain(|s| s.text.chars().any(|c| c.is_alphanumeric()));
+    before - transcript.segments.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Segment;
+
+    #[test]
+    fn test_clean_chinese() {
+        let f = DisfluencyFilter::new(Some("zh"), &[]);
+        assert_eq!(f.clean("嗯，那个，我我我觉得这个方案呃还可以"), "我觉得这个方案还可以");
+        // 非独立的 "那个" 与叠词保持不变
+        assert_eq!(f.clean("那个人谢谢大家"), "那个人谢谢大家");
+        assert_eq!(f.clean("我、我们明天开会，就是。"), "我们明天开会。");
+    }
+
+    #[test]
+    fn test_clean_english() {
+        let f = DisfluencyFilter::new(Some("en"), &[]);
+        assert_eq!(f.clean("Um, I-I think, you know, the the plan is, uh, fine."), "I think, the plan is, fine.");
+        assert_eq!(f.clean("I like that that works"), "I like that that works");
+    }
+
+    #[test]
+    fn test_clean_korean_japanese() {
+        let f = DisfluencyFilter::new(Some("ko"), &[]);
+        assert_eq!(f.clean("음 어머니가 음식을 만들었어요"), "어머니가 음식을 만들었어요");
+        assert_eq!(f.clean("어머니"), "어머니");
+        assert_eq!(f.clean("음식"), "음식");
+        assert_eq!(f.clean("얼음"), "얼음");
+        let f = DisfluencyFilter::new(Some("ja"), &[]);
+        assert_eq!(f.clean("えっと、明日です"), "明日です");
+    }
+
+    #[test]
+    fn test_extra_words_and_empty_segments() {
+        let f = DisfluencyFilter::new(Some("zh"), &["对对对".to_string()]);
+        let mut t = Transcript {
+            language: Some("zh".to_string()),
+            segments: vec![
+                Segment::new(0, 1000, "嗯。".to_string()),
+                Segment::new(1000, 3000, "对对对，开始吧".to_string()),
+            ],
+            ..Default::default()
+        };
+        let dropped = apply(&mut t, &f);
+        assert_eq!(dropped, 1);
+        assert_eq!(t.segments.len(), 1);
+        assert_eq!(t.segments[0].start_ms, 1000);
+        assert_eq!(t.segments[0].text, "开始吧");
+    }
+}
@@ -0,0 +1,267 @@
+// 命令返回的结构化错误：前端按 code 本地化提示并提供对应的修复入口，
+// params 为提示中需要的参数，detail 为底层错误原文（用于日志与排查）
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::audio::AudioError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MurmurError {
+    AppDirUnavailable { detail: String },
+    SettingsLoad { detail: String },
+    SettingsSave { detail: String },
+    SettingsInvalid { detail: String },
+    ModelsPathNotConfigured,
+    ModelNotFound { model: String, dir: String },
+    SidecarUnavailable { name: String, detail: String },
+    FfmpegSpawn { detail: String },
+    FfmpegFailed { detail: String },
+    AudioInvalid { kind: AudioError },
+    WhisperSpawn { name: String, detail: String },
+    WhisperStop { detail: String },
+    WhisperNotRunning,
+    WhisperControl { detail: String },
+    TranscriptNotFound,
+    ProfileNotFound { name: String },
+    ProfileExists { name: String },
+    ProfileNameInvalid,
+    SpeakerNameInvalid,
+    SpeakerNotFound { id: String },
+    UnsupportedKaraokeFormat { format: String },
+    UnsupportedRedactionMode { mode: String },
+    BuiltinModelUnavailable { detail: String },
+    HashFailed { path: String, detail: String },
+    ConfigInvalid { path: String, detail: String },
+    ConfigSerialize { detail: String },
+    RedactionPatternInvalid { index: usize, label: String, pattern: String, detail: String },
+    FileNotFound { path: String },
+    InvalidPath { path: String },
+    Io { path: String, detail: String },
+    Dialog { detail: String },
+    Internal { detail: String },
+}
+
+impl MurmurError {
+    pub(crate) fn io(path: impl AsRef<Path>, err: impl fmt::Display) -> Self {
+        MurmurError::Io { path: path.as_ref().display().to_string(), detail: err.to_string() }
+    }
+
+    // 稳定的错误码，前端据此选择提示文案与修复操作，不随文案变化
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            MurmurError::AppDirUnavailable { .. } => "app_dir_unavailable",
+            MurmurError::SettingsLoad { .. } => "settings_load_failed",
+            MurmurError::SettingsSave { .. } => "settings_save_failed",
+            MurmurError::SettingsInvalid { .. } => "settings_invalid",
+            MurmurError::ModelsPathNotConfigured => "models_path_not_configured",
+            MurmurError::ModelNotFound { .. } => "model_not_found",
+            MurmurError::SidecarUnavailable { .. } => "sidecar_unavailable",
+            MurmurError::FfmpegSpawn { .. } => "ffmpeg_spawn_failed",
+            MurmurError::FfmpegFailed { .. } => "ffmpeg_failed",
+            MurmurError::AudioInvalid { kind } => kind.code(),
+            MurmurError::WhisperSpawn { .. } => "whisper_spawn_failed",
+            MurmurError::WhisperStop { .. } => "whisper_stop_failed",
+            MurmurError::WhisperNotRunning => "whisper_not_running",
+            MurmurError::WhisperControl { .. } => "whisper_control_failed",
+            MurmurError::TranscriptNotFound => "transcript_not_found",
+            MurmurError::ProfileNotFound { .. } => "profile_not_found",
+            MurmurError::ProfileExists { .. } => "profile_exists",
+            MurmurError::ProfileNameInvalid => "profile_name_invalid",
+            MurmurError::SpeakerNameInvalid => "speaker_name_invalid",
+            MurmurError::SpeakerNotFound { .. } => "speaker_not_found",
+            MurmurError::UnsupportedKaraokeFormat { .. } => "unsupported_karaoke_format",
+            MurmurError::UnsupportedRedactionMode { .. } => "unsupported_redaction_mode",
+            MurmurError::BuiltinModelUnavailable { .. } => "builtin_model_unavailable",
+            MurmurError::HashFailed { .. } => "hash_failed",
+            MurmurError::ConfigInvalid { .. } => "config_invalid",
+            MurmurError::ConfigSerialize { .. } => "config_serialize_failed",
+            MurmurError::RedactionPatternInvalid { .. } => "redaction_pattern_invalid",
+            MurmurError::FileNotFound { .. } => "file_not_found",
+            MurmurError::InvalidPath { .. } => "invalid_path",
+            MurmurError::Io { .. } => "io_error",
+            MurmurError::Dialog { .. } => "dialog_failed",
+            MurmurError::Internal { .. } => "internal",
+        }
+    }
+
+    pub(crate) fn params(&self) -> BTreeMap<&'static str, String> {
+        let mut params = BTreeMap::new();
+        match self {
+            MurmurError::ModelNotFound { model, dir } => {
+                params.insert("model", model.clone());
+                params.insert("dir", dir.clone());
+            }
+            MurmurError::SidecarUnavailable { name, .. }
+            | MurmurError::WhisperSpawn { name, .. }
+            | MurmurError::ProfileNotFound { name }
+            | MurmurError::ProfileExists { name } => {
+                params.insert("name", name.clone());
+            }
+            MurmurError::AudioInvalid { kind: AudioError::UnsupportedFormat { sample_rate, channels, bits_per_sample } } => {
+                params.insert("sample_rate", sample_rate.to_string());
+                params.insert("channels", channels.to_string());
+                params.insert("bits", bits_per_sample.to_string());
+            }
+            MurmurError::SpeakerNotFound { id } => {
+                params.insert("id", id.clone());
+            }
+            MurmurError::UnsupportedKaraokeFormat { format } => {
+                params.insert("format", format.clone());
+            }
+            MurmurError::UnsupportedRedactionMode { mode } => {
+                params.insert("mode", mode.clone());
+            }
+            MurmurError::RedactionPatternInvalid { index, label, pattern, .. } => {
+                // 界面上的规则序号从 1 开始
+                params.insert("index", (index + 1).to_string());
+                params.insert("label", label.clone());
+                params.insert("pattern", pattern.clone());
+            }
+            MurmurError::FileNotFound { path }
+            | MurmurError::InvalidPath { path }
+            | MurmurError::HashFailed { path, .. }
+            | MurmurError::ConfigInvalid { path, .. }
+            | MurmurError::Io { path, .. } => {
+                params.insert("path", path.clone());
+            }
+            _ => {}
+        }
+        params
+    }
+
+    pub(crate) fn detail(&self) -> Option<&str> {
+        match self {
+            MurmurError::AppDirUnavailable { detail }
+            | MurmurError::SettingsLoad { detail }
+            | MurmurError::SettingsSave { detail }
+            | MurmurError::SettingsInvalid { detail }
+            | MurmurError::SidecarUnavailable { detail, .. }
+            | MurmurError::FfmpegSpawn { detail }
+            | MurmurError::FfmpegFailed { detail }
+            | MurmurError::AudioInvalid { kind: AudioError::Io(detail) }
+            | MurmurError::WhisperSpawn { detail, .. }
+            | MurmurError::WhisperStop { detail }
+            | MurmurError::WhisperControl { detail }
+            | MurmurError::BuiltinModelUnavailable { detail }
+            | MurmurError::HashFailed { detail, .. }
+            | MurmurError::ConfigInvalid { detail, .. }
+            | MurmurError::ConfigSerialize { detail }
+            | MurmurError::RedactionPatternInvalid { detail, .. }
+            | MurmurError::Io { detail, .. }
+            | MurmurError::Dialog { detail }
+            | MurmurError::Internal { detail } => Some(detail),
+            _ => None,
+        }
+    }
+}
+
+// 仅用于日志；界面上的提示由前端按 code 本地化，不使用这里的文字
+impl fmt::Display for MurmurError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MurmurError::AppDirUnavailable { detail } => write!(f, "获取应用目录失败: {}", detail),
+            MurmurError::SettingsLoad { detail } => write!(f, "加载设置失败: {}", detail),
+            MurmurError::SettingsSave { detail } => write!(f, "保存设置失败: {}", detail),
+            MurmurError::SettingsInvalid { detail } => write!(f, "设置无效: {}", detail),
+            MurmurError::ModelsPathNotConfigured => write!(f, "请在设置中配置 Whisper Models 路径"),
+            MurmurError::ModelNotFound { model, dir } => write!(f, "模型文件不存在: {} (Models 目录: {})", model, dir),
+            MurmurError::SidecarUnavailable { name, detail } => write!(f, "无法获取 {} sidecar: {}", name, detail),
+            MurmurError::FfmpegSpawn { detail } => write!(f, "无法执行 ffmpeg 命令: {}", detail),
+            MurmurError::FfmpegFailed { detail } => write!(f, "FFmpeg 执行失败: {}", detail),
+            MurmurError::AudioInvalid { kind } => write!(f, "音频无效: {}", kind),
+            MurmurError::WhisperSpawn { name, detail } => write!(f, "启动 {} 失败: {}", name, detail),
+            MurmurError::WhisperStop { detail } => write!(f, "停止 Whisper 失败: {}", detail),
+            MurmurError::WhisperNotRunning => write!(f, "当前没有正在进行的识别"),
+            MurmurError::WhisperControl { detail } => write!(f, "暂停或恢复 Whisper 失败: {}", detail),
+            MurmurError::TranscriptNotFound => write!(f, "未找到转写结果，请确保语音识别已完成"),
+            MurmurError::ProfileNotFound { name } => write!(f, "配置方案不存在: {}", name),
+            MurmurError::ProfileExists { name } => write!(f, "配置方案已存在: {}", name),
+            MurmurError::ProfileNameInvalid => write!(f, "配置方案名称不能为空"),
+            MurmurError::SpeakerNameInvalid => write!(f, "说话人名称不能为空"),
+            MurmurError::SpeakerNotFound { id } => write!(f, "未找到说话人: {}", id),
+            MurmurError::UnsupportedKaraokeFormat { format } => write!(f, "不支持的卡拉 OK 格式: {}", format),
+            MurmurError::UnsupportedRedactionMode { mode } => write!(f, "不支持的音频脱敏方式: {}", mode),
+            MurmurError::BuiltinModelUnavailable { detail } => write!(f, "内置模型不可用: {}", detail),
+            MurmurError::HashFailed { path, detail } => write!(f, "计算文件哈希失败: {}: {}", path, detail),
+            MurmurError::ConfigInvalid { path, detail } => write!(f, "配置文件无效: {}: {}", path, detail),
+            MurmurError::ConfigSerialize { detail } => write!(f, "序列化配置失败: {}", detail),
+            MurmurError::RedactionPatternInvalid { label, detail, .. } => write!(f, "脱敏规则 \"{}\" 无效: {}", label, detail),
+            MurmurError::FileNotFound { path } => write!(f, "文件不存在: {}", path),
+            MurmurError::InvalidPath { path } => write!(f, "无效的路径: {}", path),
+            MurmurError::Io { path, detail } => write!(f, "文件操作失败: {}: {}", path, detail),
+            MurmurError::Dialog { detail } => write!(f, "对话框操作失败: {}", detail),
+            MurmurError::Internal { detail } => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl std::error::Error for MurmurError {}
+
+// 各模块仍以 String 描述错误，未细分的统一归为 internal
+impl From<String> for MurmurError {
+    fn from(detail: String) -> Self {
+        MurmurError::Internal { detail }
+    }
+}
+
+impl From<AudioError> for MurmurError {
+    fn from(kind: AudioError) -> Self {
+        MurmurError::AudioInvalid { kind }
+    }
+}
+
+impl From<crate::redaction::InvalidPattern> for MurmurError {
+    fn from(e: crate::redaction::InvalidPattern) -> Self {
+        MurmurError::RedactionPatternInvalid { index: e.index, label: e.label, pattern: e.pattern, detail: e.detail }
+    }
+}
+
+impl From<&str> for MurmurError {
+    fn from(detail: &str) -> Self {
+        MurmurError::Internal { detail: detail.to_string() }
+    }
+}
+
+// 序列化为 { code, params, detail }
+impl Serialize for MurmurError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MurmurError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("params", &self.params())?;
+        state.serialize_field("detail", &self.detail())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_shape() {
+        let err = MurmurError::ModelNotFound { model: "ggml-base.bin".to_string(), dir: "/models".to_string() };
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({"code": "model_not_found", "params": {"model": "ggml-base.bin", "dir": "/models"}, "detail": null})
+        );
+
+        let err = MurmurError::io("/tmp/a.wav", "permission denied");
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["code"], "io_error");
+        assert_eq!(value["params"]["path"], "/tmp/a.wav");
+        assert_eq!(value["detail"], "permission denied");
+
+        let err = MurmurError::SpeakerNotFound { id: "SPEAKER_02".to_string() };
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["code"], "speaker_not_found");
+        assert_eq!(value["params"]["id"], "SPEAKER_02");
+
+        let err: MurmurError = AudioError::UnsupportedFormat { sample_rate: 44100, channels: 2, bits_per_sample: 16 }.into();
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["code"], "audio_unsupported_format");
+        assert_eq!(value["params"]["sample_rate"], "44100");
+        assert_eq!(value["detail"], serde_json::Value::Null);
+    }
+}
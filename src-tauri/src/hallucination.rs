@@ -0,0 +1,313 @@
+// 幻觉与重复检测：whisper 在静音/音乐段落容易循环输出同一句，
+// 或生成 "请不吝点赞 订阅 转发"、"Thanks for watching" 之类的字幕残留
+use crate::audio::EnergyProfile;
+use crate::opencc::{self, ScriptTarget};
+use crate::transcript::{is_cjk, primary_language, FlagReason, SegmentFlag, Transcript};
+
+// 连续相同文本达到该次数才视为循环（保留第一次）
+const MIN_REPEAT_RUN: usize = 3;
+// 段内同一短语循环出现的最少次数
+const MIN_INNER_REPEATS: usize = 4;
+// 段内最大窗口能量低于该值（dBFS）视为静音
+const SILENCE_DB: f32 = -45.0;
+// 语速上限：中日韩文字按字计，其他语言按词计
+const MAX_CJK_CHARS_PER_SEC: f64 = 12.0;
+const MAX_WORDS_PER_SEC: f64 = 7.0;
+// 过短的段落不计算语速，只要有文字即视为异常
+const MIN_SEGMENT_MS: i64 = 100;
+
+// 已知的幻觉短语（按语言），比较时会先做归一化
+const KNOWN_PHRASES: &[(&str, &[&str])] = &[
+    ("zh", &[
+        "请不吝点赞订阅转发打赏支持明镜与点点栏目",
+        "请不吝点赞订阅转发",
+        "点赞订阅转发打赏",
+        "明镜与点点栏目",
+        "字幕由amara.org社区提供",
+        "中文字幕志愿者",
+        "字幕志愿者",
+        "谢谢观看",
+        "感谢观看",
+        "谢谢收看",
+        "感谢收看",
+        "谢谢大家收看",
+        "请订阅我的频道",
+        "欢迎订阅我的频道",
+        "优优独播剧场",
+    ]),
+    ("en", &[
+        "thanks for watching",
+        "thank you for watching",
+        "please subscribe",
+        "subscribe to my channel",
+        "like and subscribe",
+        "see you in the next video",
+        "subtitles by the amara.org community",
+        "transcription by castingwords",
+    ]),
+    ("ja", &[
+        "ご視聴ありがとうございました",
+        "チャンネル登録お願いします",
+        "チャンネル登録よろしくお願いします",
+    ]),
+    ("ko", &[
+        "시청해주셔서 감사합니다",
+        "구독과 좋아요",
+    ]),
+    ("es", &[
+        "gracias por ver el video",
+        "subtítulos realizados por la comunidad de amara.org",
+    ]),
+    ("fr", &[
+        "merci d'avoir regardé",
+        "sous-titres réalisés par la communauté d'amara.org",
+    ]),
+    ("de", &[
+        "untertitel der amara.org-community",
+        "untertitelung des zdf",
+    ]),
+    ("ru", &[
+        "спасибо за просмотр",
+        "продолжение следует",
+        "субтитры сделал dimatorzok",
+    ]),
+];
+
+// 检测结果的处理方式，对应设置项 hallucination_filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilterMode {
+    Mark,
+    Drop,
+}
+
+impl FilterMode {
+    // "off" 或未知取值返回 None，表示不做检测
+    pub(crate) fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "mark" => Some(Self::Mark),
+            "drop" => Some(Self::Drop),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Summary {
+    pub(crate) repetition: usize,
+    pub(crate) known_phrase: usize,
+    pub(crate) silence: usize,
+    pub(crate) speech_rate: usize,
+    pub(crate) flagged: usize,
+    pub(crate) dropped: usize,
+}
+
+impl Summary {
+    pub(crate) fn describe(&self) -> String {
+        format!(
+            "幻觉检测: 标记 {} 段（重复 {}，已知短语 {}，静音 {}，语速异常 {}），删除 {} 段",
+            self.flagged, self.repetition, self.known_phrase, self.silence, self.speech_rate, self.dropped
+        )
+    }
+}
+
+// 归一化：统一为简体、小写，去掉空白与标点
+fn normalize(text: &str) -> String {
+    opencc::convert(text, ScriptTarget::Hans)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+// 朗读单位数：中日韩文字逐字计数，其余按空白分词
+fn speech_units(text: &str) -> (usize, bool) {
+    let cjk = text.chars().filter(|c| is_cjk(*c)).count();
+    let words = text
+        .split(|c: char| c.is_whitespace() || is_cjk(c))
+        .filter(|w| w.chars().any(|c| c.is_alphanumeric()))
+        .count();
+    (cjk + words, cjk >= words)
+}
+
+// 段内循环：整段由同一短语（至少 2 个字符）重复若干次构成
+fn inner_repeat(normalized: &str) -> Option<(String, usize)> {
+    let chars: Vec<char> = normalized.chars().collect();
+    let n = chars.len();
+    for unit in 2..=n / MIN_INNER_REPEATS {
+        if !n.is_multiple_of(unit) {
+            continue;
+        }
+        let head = &chars[..unit];
+        if chars.chunks(unit).all(|c| c == head) {
+            return Some((head.iter().collect(), n / unit));
+        }
+    }
+    None
+}
+
+fn phrases_for(language: Option<&str>) -> Vec<&'static str> {
+    let lang = language.map(primary_language);
+    let known = lang
+        .as_deref()
+        .filter(|l| KNOWN_PHRASES.iter().any(|(k, _)| k == l));
+    KNOWN_PHRASES
+        .iter()
+        .filter(|(k, _)| known.is_none_or(|l| *k == l))
+        .flat_map(|(_, list)| list.iter().cloned())
+        .collect()
+}
+
+// 短语需占据该段文本的大部分，避免误伤正常语句中的引用
+fn matches_known_phrase(normalized: &str, phrases: &[String]) -> Option<String> {
+    let len = normalized.chars().count();
+    if len == 0 {
+        return None;
+    }
+    phrases
+        .iter()
+        .find(|p| normalized.contains(p.as_str()) && p.chars().count() * 10 >= len * 6)
+        .cloned()
+}
+
+fn flag(kind: &str, reason: FlagReason) -> SegmentFlag {
+    SegmentFlag { kind: kind.to_string(), reason }
+}
+
+pub(crate) fn analyze(
+    transcript: &mut Transcript,
+    energy: Option<&EnergyProfile>,
+    language: Option<&str>,
+    mode: FilterMode,
+) -> Summary {
+    let mut summary = Summary::default();
+    let language = transcript.language.clone().or_else(|| language.map(|s| s.to_string()));
+    let phrases: Vec<String> = phrases_for(language.as_deref()).iter().map(|p| normalize(p)).collect();
+    let normalized: Vec<String> = transcript.segments.iter().map(|s| normalize(&s.text)).collect();
+
+    // 连续重复：找出长度达到阈值的相同文本段落
+    let mut i = 0;
+    while i < normalized.len() {
+        let mut j = i + 1;
+        while j < normalized.len() && !normalized[i].is_empty() && normalized[j] == normalized[i] {
+            j += 1;
+        }
+        if j - i >= MIN_REPEAT_RUN {
+            for k in i + 1..j {
+                transcript.segments[k].flags.push(flag("repetition", FlagReason::RepeatPrevious { count: j - i }));
+                summary.repetition += 1;
+            }
+        }
+        i = j;
+    }
+
+    for (idx, seg) in transcript.segments.iter_mut().enumerate() {
+        let norm = &normalized[idx];
+        if norm.is_empty() {
+            continue;
+        }
+
+        if let Some((unit, times)) = inner_repeat(norm) {
+            seg.flags.push(flag("repetition", FlagReason::RepeatInner { phrase: unit, count: times }));
+            summary.repetition += 1;
+        }
+
+        if let Some(p) = matches_known_phrase(norm, &phrases) {
+            seg.flags.push(flag("known_phrase", FlagReason::KnownPhrase { phrase: p }));
+            summary.known_phrase += 1;
+        }
+
+        if let Some(db) = energy.and_then(|e| e.max_db(seg.start_ms, seg.end_ms)) {
+            if db < SILENCE_DB {
+                seg.flags.push(flag("silence", FlagReason::Silence { peak_db: db }));
+                summary.silence += 1;
+            }
+        }
+
+        let duration_ms = seg.end_ms - seg.start_ms;
+        let (units, cjk) = speech_units(&seg.text);
+        if duration_ms < MIN_SEGMENT_MS {
+            seg.flags.push(flag("speech_rate", FlagReason::TooShort { duration_ms: duration_ms.max(0) }));
+            summary.speech_rate += 1;
+        } else {
+            let rate = units as f64 / (duration_ms as f64 / 1000.0);
+            let limit = if cjk { MAX_CJK_CHARS_PER_SEC } else { MAX_WORDS_PER_SEC };
+            if rate > limit {
+                let unit = if cjk { "chars" } else { "words" };
+                seg.flags.push(flag("speech_rate", FlagReason::SpeechRate { rate, limit, unit: unit.to_string() }));
+                summary.speech_rate += 1;
+            }
+        }
+    }
+
+    summary.flagged = transcript.segments.iter().filter(|s| !s.flags.is_empty()).count();
+    if mode == FilterMode::Drop {
+        let before = transcript.segments.len();
+        transcript.segments.retain(|s| s.flags.is_empty());
+        summary.dropped = before - transcript.segments.len();
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Segment;
+
+    fn transcript(items: &[(i64, i64, &str)]) -> Transcript {
+        Transcript {
+            language: Some("zh".to_string()),
+            segments: items.iter().map(|(s, e, t)| Segment::new(*s, *e, t.to_string())).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consecutive_repeats_keep_first() {
+        let mut t = transcript(&[
+            (0, 2000, "今天开会"),
+            (2000, 4000, "我们继续。"),
+            (4000, 6000, "我们继续"),
+            (6000, 8000, "我们继续！"),
+        ]);
+        let mut marked = t.clone();
+        analyze(&mut marked, None, None, FilterMode::Mark);
+        assert_eq!(marked.segments[3].flags[0].reason, FlagReason::RepeatPrevious { count: 3 });
+
+        let summary = analyze(&mut t, None, None, FilterMode::Drop);
+        assert_eq!(summary.repetition, 2);
+        assert_eq!(summary.dropped, 2);
+        assert_eq!(t.segments.len(), 2);
+        assert_eq!(t.segments[1].text, "我们继续。");
+    }
+
+    #[test]
+    fn test_known_phrase_and_traditional_input() {
+        let mut t = transcript(&[
+            (0, 3000, "請不吝點贊 訂閱 轉發 打賞支持明鏡與點點欄目"),
+            (3000, 6000, "这个方案谢谢观看的人提出了意见，我们需要讨论"),
+        ]);
+        let summary = analyze(&mut t, None, None, FilterMode::Mark);
+        assert_eq!(summary.known_phrase, 1);
+        assert_eq!(t.segments[0].flags[0].kind, "known_phrase");
+        assert!(t.segments[1].flags.is_empty());
+    }
+
+    #[test]
+    fn test_speech_rate_and_silence() {
+        let mut t = transcript(&[
+            (0, 500, "这是一个非常非常长的句子不可能在半秒说完"),
+            (1000, 3000, "正常语速"),
+        ]);
+        let energy = EnergyProfile { window_ms: 500, rms: vec![0.2, 0.2, 0.0001, 0.0001, 0.0001, 0.0001] };
+        let summary = analyze(&mut t, Some(&energy), None, FilterMode::Mark);
+        assert_eq!(summary.speech_rate, 1);
+        assert_eq!(summary.silence, 1);
+        assert_eq!(t.segments[1].flags[0].kind, "silence");
+    }
+
+    #[test]
+    fn test_inner_repeat() {
+        assert_eq!(inner_repeat("谢谢你谢谢你谢谢你谢谢你"), Some(("谢谢你".to_string(), 4)));
+        assert_eq!(inner_repeat("哈哈哈哈"), None);
+    }
+}
@@ -0,0 +1,165 @@
+// 后端文案目录：事件内容、处理结果说明、系统信息与对话框标题按 app_locale 选择语言。
+// 查找顺序：完全匹配的语言 -> 同语种（如 en-US -> en）-> 英文 -> 默认的简体中文，均缺失时返回键名
+pub(crate) const DEFAULT_LOCALE: &str = "zh-CN";
+const FALLBACK_LOCALE: &str = "en";
+
+type Catalog = &'static [(&'static str, &'static str)];
+
+const ZH_CN: Catalog = &[
+    ("process.cached_audio", "已使用缓存的音频，准备开始语音识别..."),
+    ("process.no_conversion", "文件无需转换，准备开始语音识别..."),
+    ("process.converted", "文件转换成功，准备开始语音识别..."),
+    ("whisper.cached_result", "已使用缓存的识别结果"),
+    ("whisper.complete", "Whisper 识别完成"),
+    ("whisper.postprocess_failed", "后处理失败，详情见任务日志"),
+    ("whisper.exit_code", "Whisper 进程异常退出: {code}"),
+    ("whisper.redaction_failed", "脱敏失败，未脱敏的识别结果已删除，详情见任务日志"),
+    ("whisper.process_error", "Whisper 进程错误: {error}"),
+    ("speaker.default_name", "说话人 {id}"),
+    ("dialog.select_directory", "选择目录"),
+    ("dialog.select_media", "选择视频或音频文件"),
+    ("dialog.media_filter", "媒体文件"),
+    ("system.unknown_os", "未知系统"),
+    ("system.unknown_version", "未知版本"),
+    ("system.unknown_cpu", "未知 CPU"),
+    ("system.cpu_cores", "{count} 核心"),
+    ("system.gpu_apple", "Apple GPU (通过系统检测)"),
+    ("system.gpu_unknown", "GPU 信息需要额外检测"),
+    ("system.ffmpeg_unavailable", "FFmpeg 不可用"),
+    ("vulkan.load_failed", "加载 Vulkan 失败: {error}"),
+    ("vulkan.instance_failed", "创建 Vulkan 实例失败: {error}"),
+    ("vulkan.enumerate_failed", "枚举物理设备失败: {error}"),
+    ("settings.invalid_choice", "不支持的取值: {value}（可选: {allowed}）"),
+    ("settings.out_of_range", "取值应在 {min} 到 {max} 之间"),
+    ("settings.clamped", "已调整为 {value}"),
+    ("settings.empty", "不能为空"),
+    ("settings.path_not_found", "路径不存在: {value}"),
+    ("settings.unsupported_platform", "当前平台不支持: {value}"),
+    ("settings.invalid_value", "无效的设置: {value}"),
+    ("settings.redaction_pattern_invalid", "第 {index} 条脱敏规则 {label} 无效: {pattern}"),
+    ("settings.reset_to_default", "已恢复为默认值"),
+    ("settings.diarization_unavailable", "当前版本未包含说话人聚类功能"),
+    ("settings.vad_max_speech_too_short", "最长语音时长不能小于最短语音时长"),
+];
+
+const EN: Catalog = &[
+    ("process.cached_audio", "Using cached audio, starting speech recognition..."),
+    ("process.no_conversion", "No conversion needed, starting speech recognition..."),
+    ("process.converted", "File converted, starting speech recognition..."),
+    ("whisper.cached_result", "Using cached recognition result"),
+    ("whisper.complete", "Whisper recognition completed"),
+    ("whisper.postprocess_failed", "Post-processing failed, see the job log for details"),
+    ("whisper.exit_code", "Whisper process failed with exit code {code}"),
+    ("whisper.redaction_failed", "Redaction failed and the unredacted results were deleted, see the job log for details"),
+    ("whisper.process_error", "Whisper process error: {error}"),
+    ("speaker.default_name", "Speaker {id}"),
+    ("dialog.select_directory", "Select Folder"),
+    ("dialog.select_media", "Select a Video or Audio File"),
+    ("dialog.media_filter", "Media Files"),
+    ("system.unknown_os", "Unknown"),
+    ("system.unknown_version", "Unknown version"),
+    ("system.unknown_cpu", "Unknown CPU"),
+    ("system.cpu_cores", "{count} cores"),
+    ("system.gpu_apple", "Apple GPU (detected by system)"),
+    ("system.gpu_unknown", "GPU info requires additional detection"),
+    ("system.ffmpeg_unavailable", "FFmpeg unavailable"),
+    ("vulkan.load_failed", "Failed to load Vulkan: {error}"),
+    ("vulkan.instance_failed", "Failed to create Vulkan instance: {error}"),
+    ("vulkan.enumerate_failed", "Failed to enumerate physical devices: {error}"),
+    ("settings.invalid_choice", "Unsupported value: {value} (allowed: {allowed})"),
+    ("settings.out_of_range", "Value must be between {min} and {max}"),
+    ("settings.clamped", "Adjusted to {value}"),
+    ("settings.empty", "Must not be empty"),
+    ("settings.path_not_found", "Path does not exist: {value}"),
+    ("settings.unsupported_platform", "Not supported on this platform: {value}"),
+    ("settings.invalid_value", "Invalid setting: {value}"),
+    ("settings.redaction_pattern_invalid", "Redaction rule #{index} {label} is invalid: {pattern}"),
+    ("settings.reset_to_default", "reset to default"),
+    ("settings.diarization_unavailable", "Speaker clustering is not included in this build"),
+    ("settings.vad_max_speech_too_short", "Maximum speech duration must not be shorter than the minimum speech duration"),
+];
+
+const CATALOGS: &[(&str, Catalog)] = &[("zh-CN", ZH_CN), ("en", EN)];
+
+fn language(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+// 依次列出要查找的目录，去掉重复项
+fn fallback_chain(locale: &str) -> Vec<Catalog> {
+    let locale = locale.trim().replace('_', "-");
+    let exact = CATALOGS.iter().find(|(tag, _)| tag.eq_ignore_ascii_case(&locale));
+    let same_language = CATALOGS
+        .iter()
+        .find(|(tag, _)| language(tag).eq_ignore_ascii_case(language(&locale)));
+    let fallback = CATALOGS.iter().find(|(tag, _)| *tag == FALLBACK_LOCALE);
+    let default = CATALOGS.iter().find(|(tag, _)| *tag == DEFAULT_LOCALE);
+
+    let mut chain: Vec<Catalog> = Vec::new();
+    for (_, catalog) in [exact, same_language, fallback, default].into_iter().flatten() {
+        if !chain.iter().any(|c| std::ptr::eq(*c, *catalog)) {
+            chain.push(catalog);
+        }
+    }
+    chain
+}
+
+#[derive(Clone)]
+pub(crate) struct Messages {
+    chain: Vec<Catalog>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCALE)
+    }
+}
+
+impl Messages {
+    pub(crate) fn new(locale: &str) -> Self {
+        Self { chain: fallback_chain(locale) }
+    }
+
+    fn lookup(&self, key: &str) -> Option<&'static str> {
+        self.chain
+            .iter()
+            .find_map(|catalog| catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v))
+    }
+
+    pub(crate) fn get(&self, key: &str) -> String {
+        self.lookup(key).unwrap_or(key).to_string()
+    }
+
+    // 替换文案中的 {name} 占位符
+    pub(crate) fn format(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        let mut text = self.get(key);
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_chain() {
+        assert_eq!(Messages::new("en").get("whisper.complete"), "Whisper recognition completed");
+        assert_eq!(Messages::new("en_US").get("dialog.select_directory"), "Select Folder");
+        assert_eq!(Messages::new("zh-TW").get("dialog.select_directory"), "选择目录");
+        assert_eq!(Messages::new("fr").get("dialog.select_directory"), "Select Folder");
+        assert_eq!(Messages::default().get("missing.key"), "missing.key");
+        assert_eq!(
+            Messages::new("en").format("system.cpu_cores", &[("count", &8)]),
+            "8 cores"
+        );
+
+        // 两种语言的目录应覆盖相同的键
+        for (key, _) in ZH_CN {
+            assert!(EN.iter().any(|(k, _)| k == key), "en 缺少 {}", key);
+        }
+        assert_eq!(ZH_CN.len(), EN.len());
+    }
+}
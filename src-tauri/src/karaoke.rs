@@ -0,0 +1,269 @@
+// 卡拉 OK 字幕：基于词级时间戳生成 Enhanced LRC（<mm:ss.xx> 逐词）与带 \k 标签的 ASS
+use crate::transcript::{format_lrc_timestamp, is_cjk, Transcript, Word};
+
+pub(crate) const DEFAULT_MAX_LINE_CHARS: usize = 32;
+
+// 媒体文件中的标签，用于 LRC 的 [ti:]、[ar:]、[al:] 头
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct MediaTags {
+    pub(crate) title: Option<String>,
+    pub(crate) artist: Option<String>,
+    pub(crate) album: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct KaraokeOptions {
+    // LRC 的 [offset:] 标签（毫秒），正值表示歌词提前显示；ASS 直接平移时间轴
+    pub(crate) offset_ms: i64,
+    pub(crate) max_line_chars: usize,
+    pub(crate) tags: MediaTags,
+}
+
+impl Default for KaraokeOptions {
+    fn default() -> Self {
+        Self { offset_ms: 0, max_line_chars: DEFAULT_MAX_LINE_CHARS, tags: MediaTags::default() }
+    }
+}
+
+// 解析 `ffmpeg -i <media> -f ffmetadata -` 的输出，例如：
+// ;FFMETADATA1
+// title=Song\=Name
+// artist=Someone
+pub(crate) fn parse_ffmetadata(content: &str) -> MediaTags {
+    let mut tags = MediaTags::default();
+    let mut album_artist = None;
+    for line in content.lines() {
+        if line.starts_with(';') || line.starts_with('[') {
+            // 注释行，或 [STREAM]/[CHAPTER] 段落（只取全局标签）
+            if line.starts_with('[') {
+                break;
+            }
+            continue;
+        }
+        let Some((key, value)) = split_unescaped(line) else {
+            continue;
+        };
+        let value = unescape(value).trim().to_string();
+        if value.is_empty() {
+            continue;
+        }
+        match key.to_ascii_lowercase().as_str() {
+            "title" => tags.title = Some(value),
+            "artist" => tags.artist = Some(value),
+            "album_artist" => album_artist = Some(value),
+            "album" => tags.album = Some(value),
+            _ => {}
+        }
+    }
+    if tags.artist.is_none() {
+        tags.artist = album_artist;
+    }
+    tags
+}
+
+// 在第一个未转义的 '=' 处切分
+fn split_unescaped(line: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '=' if !escaped => return Some((&line[..i], &line[i + 1..])),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            out.extend(chars.next());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// 中日韩文字之间不加空格，其余词之间用空格分隔
+fn needs_space(prev: &str, next: &str) -> bool {
+    let prev_cjk = prev.chars().last().is_some_and(is_cjk);
+    let next_cjk = next.chars().next().is_some_and(is_cjk);
+    let next_punct = next.chars().next().is_some_and(|c| !c.is_alphanumeric());
+    !(prev_cjk || next_cjk || next_punct)
+}
+
+// 按段落与最大字符数切分为行；没有词级时间戳的段落整段作为一个词
+fn split_lines(transcript: &Transcript, max_line_chars: usize) -> Vec<Vec<Word>> {
+    let max = max_line_chars.max(1);
+    let mut lines = Vec::new();
+    for seg in &transcript.segments {
+        let words = if seg.words.is_empty() {
+            vec![Word { start_ms: seg.start_ms, end_ms: seg.end_ms, text: seg.text.clone(), probability: 1.0 }]
+        } else {
+            seg.words.clone()
+        };
+        let mut line: Vec<Word> = Vec::new();
+        let mut len = 0;
+        for w in words.into_iter().filter(|w| !w.text.is_empty()) {
+            let space = line.last().is_some_and(|p| needs_space(&p.text, &w.text)) as usize;
+            let add = w.text.chars().count() + space;
+            if !line.is_empty() && len + add > max {
+                lines.push(std::mem::take(&mut line));
+                len = 0;
+            }
+            len += if line.is_empty() { w.text.chars().count() } else { add };
+            line.push(w);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+fn lrc_headers(options: &KaraokeOptions) -> String {
+    let mut out = String::new();
+    let tags = &options.tags;
+    for (tag, value) in [("ti", &tags.title), ("ar", &tags.artist), ("al", &tags.album)] {
+        if let Some(v) = value {
+            out.push_str(&format!("[{}:{}]\n", tag, v));
+        }
+    }
+    if options.offset_ms != 0 {
+        out.push_str(&format!("[offset:{:+}]\n", options.offset_ms));
+    }
+    out.push_str("[by:whisper.cpp]\n");
+    out
+}
+
+// [00:12.34]<00:12.34>Hello <00:12.80>world<00:13.50>
+pub(crate) fn render_enhanced_lrc(transcript: &Transcript, options: &KaraokeOptions) -> String {
+    let mut out = lrc_headers(options);
+    for line in split_lines(transcript, options.max_line_chars) {
+        out.push_str(&format!("[{}]", format_lrc_timestamp(line[0].start_ms)));
+        for (i, w) in line.iter().enumerate() {
+            if i > 0 && needs_space(&line[i - 1].text, &w.text) {
+                out.push(' ');
+            }
+            out.push_str(&format!("<{}>{}", format_lrc_timestamp(w.start_ms), w.text));
+        }
+        let end = line.iter().map(|w| w.end_ms).max().unwrap_or(0);
+        out.push_str(&format!("<{}>\n", format_lrc_timestamp(end)));
+    }
+    out
+}
+
+// 0:00:12.34
+fn format_ass_time(ms: i64) -> String {
+    let ms = ms.max(0);
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        ms / 3_600_000,
+        (ms % 3_600_000) / 60_000,
+        (ms % 60_000) / 1000,
+        (ms % 1000) / 10
+    )
+}
+
+// ASS 中花括号用于样式标签，文本中的花括号替换为圆括号
+fn escape_ass(text: &str) -> String {
+    text.replace('{', "(").replace('}', ")").replace('\n', "\\N")
+}
+
+// [Script Info] 之后的固定内容，标题单独写入
+const ASS_HEADER: &str = "ScriptType: v4.00+
+PlayResX: 1920
+PlayResY: 1080
+WrapStyle: 2
+ScaledBorderAndShadow: yes
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,64,&H0000FFFF,&H00FFFFFF,&H00000000,&H64000000,0,0,0,0,100,100,0,0,1,3,0,2,60,60,60,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+";
+
+// 每个词的 \k 时长（厘秒）取到下一个词开始，最后一个词取到行尾
+pub(crate) fn render_ass(transcript: &Transcript, options: &KaraokeOptions) -> String {
+    let mut out = String::from("[Script Info]\n");
+    if let Some(title) = &options.tags.title {
+        out.push_str(&format!("Title: {}\n", title));
+    }
+    out.push_str(ASS_HEADER);
+    let shift = |ms: i64| (ms - options.offset_ms).max(0);
+    for line in split_lines(transcript, options.max_line_chars) {
+        let start = line[0].start_ms;
+        let end = line.iter().map(|w| w.end_ms).max().unwrap_or(start);
+        let mut text = String::new();
+        for (i, w) in line.iter().enumerate() {
+            let next = line.get(i + 1).map_or(end, |n| n.start_ms);
+            let centis = ((next - w.start_ms).max(0) + 5) / 10;
+            if i > 0 && needs_space(&line[i - 1].text, &w.text) {
+                text.push(' ');
+            }
+            text.push_str(&format!("{{\\k{}}}{}", centis, escape_ass(&w.text)));
+        }
+        out.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            format_ass_time(shift(start)),
+            format_ass_time(shift(end)),
+            text
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Segment;
+
+    fn word(start_ms: i64, end_ms: i64, text: &str) -> Word {
+        Word { start_ms, end_ms, text: text.to_string(), probability: 0.9 }
+    }
+
+    fn sample() -> Transcript {
+        let mut en = Segment::new(12_340, 14_000, "Hello world again".to_string());
+        en.words = vec![word(12_340, 12_800, "Hello"), word(12_800, 13_500, "world"), word(13_600, 14_000, "again")];
+        let mut zh = Segment::new(20_000, 21_000, "你好".to_string());
+        zh.words = vec![word(20_000, 20_500, "你"), word(20_500, 21_000, "好")];
+        Transcript { segments: vec![en, zh], ..Default::default() }
+    }
+
+    #[test]
+    fn test_enhanced_lrc() {
+        let options = KaraokeOptions {
+            offset_ms: -200,
+            max_line_chars: 11,
+            tags: MediaTags { title: Some("Song".to_string()), artist: Some("Band".to_string()), album: None },
+        };
+        let lrc = render_enhanced_lrc(&sample(), &options);
+        assert!(lrc.starts_with("[ti:Song]\n[ar:Band]\n[offset:-200]\n"));
+        // "Hello world" 恰好 11 个字符，"again" 换到下一行
+        assert!(lrc.contains("[00:12.34]<00:12.34>Hello <00:12.80>world<00:13.50>\n"));
+        assert!(lrc.contains("[00:13.60]<00:13.60>again<00:14.00>\n"));
+        assert!(lrc.contains("[00:20.00]<00:20.00>你<00:20.50>好<00:21.00>\n"));
+    }
+
+    #[test]
+    fn test_ass_karaoke() {
+        let options = KaraokeOptions { offset_ms: 340, ..Default::default() };
+        let ass = render_ass(&sample(), &options);
+        assert!(ass.contains("[Events]"));
+        assert!(ass.contains("Dialogue: 0,0:00:12.00,0:00:13.66,Default,,0,0,0,,{\\k46}Hello {\\k80}world {\\k40}again\n"));
+        assert!(ass.contains("{\\k50}你{\\k50}好"));
+    }
+
+    #[test]
+    fn test_parse_ffmetadata() {
+        let tags = parse_ffmetadata(";FFMETADATA1\ntitle=A\\=B\nalbum_artist=Band\nALBUM=Live\n[CHAPTER]\ntitle=Chapter 1\n");
+        assert_eq!(tags.title.as_deref(), Some("A=B"));
+        assert_eq!(tags.artist.as_deref(), Some("Band"));
+        assert_eq!(tags.album.as_deref(), Some("Live"));
+    }
+}
@@ -5,9 +5,35 @@ use std::ffi::CString;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use error::MurmurError;
+
+mod audio;
+mod cache;
+mod config_bundle;
+mod cpu;
+mod diarization;
+mod disfluency;
+mod error;
+mod hallucination;
+mod i18n;
+mod karaoke;
+mod opencc;
+mod paths;
+mod priority;
+mod profiles;
+mod redaction;
+mod settings_file;
+mod storage;
+mod transcript;
+mod validation;
+mod vad;
+mod workspace;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct AppSettings {
+    // 设置结构版本，读取旧文件时据此逐步迁移
+    #[serde(default)]
+    schema_version: u32,
     whisper_models_path: Option<String>,
     #[serde(default = "default_app_locale")]
     app_locale: String,
@@ -17,12 +43,72 @@ struct AppSettings {
     whisper_model: String,
     #[serde(default)]
     enable_vad: bool,
+    #[serde(default)]
+    vad: vad::VadSettings,
     #[serde(default = "default_whisper_optimization")]
     whisper_optimization: String,
     #[serde(default)]
     disable_gpu: bool,
+    // 0 表示按 CPU 自动选择
     #[serde(default = "default_thread_count")]
     thread_count: u32,
+    // whisper-cli 的 --processors，把音频分段并行识别
+    #[serde(default = "default_processor_count")]
+    processor_count: u32,
+    // 以较低的调度优先级运行 whisper-cli 与 ffmpeg
+    #[serde(default)]
+    background_priority: bool,
+    // 其他程序的 CPU 占用超过该百分比时暂停识别，0 表示不限制
+    #[serde(default)]
+    cpu_cap_percent: u32,
+    #[serde(default = "default_output_script")]
+    output_script: String,
+    #[serde(default = "default_hallucination_filter")]
+    hallucination_filter: String,
+    #[serde(default)]
+    disfluency_filter: bool,
+    #[serde(default)]
+    disfluency_extra_words: Vec<String>,
+    #[serde(default)]
+    redaction_enabled: bool,
+    #[serde(default)]
+    redaction_custom_patterns: Vec<redaction::CustomPattern>,
+    #[serde(default = "default_redaction_audio_mode")]
+    redaction_audio_mode: String,
+    #[serde(default = "default_word_timestamps")]
+    word_timestamps: bool,
+    #[serde(default)]
+    dtw_timestamps: bool,
+    #[serde(default = "default_low_confidence_threshold")]
+    low_confidence_threshold: f32,
+    #[serde(default)]
+    stereo_diarize: bool,
+    #[serde(default)]
+    stereo_channel_names: Vec<String>,
+    #[serde(default)]
+    speaker_clustering: bool,
+    #[serde(default)]
+    speaker_model_path: Option<String>,
+    #[serde(default = "default_speaker_model_input")]
+    speaker_model_input: String,
+    #[serde(default)]
+    speaker_count: u32,
+    #[serde(default = "default_speaker_similarity_threshold")]
+    speaker_similarity_threshold: f32,
+    #[serde(default = "default_job_keep_last")]
+    job_keep_last: u32,
+    #[serde(default = "default_job_max_age_days")]
+    job_max_age_days: u32,
+    #[serde(default = "default_job_max_total_mb")]
+    job_max_total_mb: u64,
+    // 临时目录（任务工作区）位置，为空时使用应用数据目录下的 temp
+    #[serde(default)]
+    temp_dir_path: Option<String>,
+    #[serde(default = "default_result_cache_enabled")]
+    result_cache_enabled: bool,
+    // 结果缓存容量上限（MB），0 表示不限制
+    #[serde(default = "default_result_cache_max_mb")]
+    result_cache_max_mb: u64,
 }
 
 fn default_whisper_language() -> String {
@@ -44,21 +130,236 @@ fn default_whisper_optimization() -> String {
     "none".to_string()
 }
 
-fn default_thread_count() -> u32 { 4 }
+fn default_thread_count() -> u32 { cpu::AUTO_THREADS }
+
+fn default_processor_count() -> u32 { 1 }
+
+fn default_output_script() -> String {
+    // 可选: "keep" | "zh-Hans" | "zh-Hant" | "zh-TW" | "zh-HK"
+    "keep".to_string()
+}
+
+fn default_hallucination_filter() -> String {
+    // 可选: "off" | "mark" | "drop"；默认关闭，需要时由用户开启
+    "off".to_string()
+}
+
+fn default_redaction_audio_mode() -> String {
+    // 可选: "beep" | "silence"
+    "beep".to_string()
+}
+
+// 词级时间戳需要 --output-json-full，输出更大，默认关闭
+fn default_word_timestamps() -> bool { false }
+
+fn default_low_confidence_threshold() -> f32 { 0.5 }
+
+fn default_speaker_model_input() -> String {
+    // 可选: "fbank" | "waveform"
+    "fbank".to_string()
+}
+
+fn default_speaker_similarity_threshold() -> f32 { 0.5 }
+
+// 任务工作区保留规则，0 表示不限制
+fn default_job_keep_last() -> u32 { 20 }
+
+fn default_job_max_age_days() -> u32 { 7 }
+
+fn default_job_max_total_mb() -> u64 { 4096 }
+
+fn default_result_cache_enabled() -> bool { true }
+
+fn default_result_cache_max_mb() -> u64 { 2048 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
+            schema_version: settings_file::SCHEMA_VERSION,
             whisper_models_path: None,
             app_locale: default_app_locale(),
             whisper_language: "auto".to_string(),
             whisper_model: default_whisper_model(),
             enable_vad: false,
+            vad: vad::VadSettings::default(),
             whisper_optimization: default_whisper_optimization(),
             disable_gpu: false,
             thread_count: default_thread_count(),
+            processor_count: default_processor_count(),
+            background_priority: false,
+            cpu_cap_percent: 0,
+            output_script: default_output_script(),
+            hallucination_filter: default_hallucination_filter(),
+            disfluency_filter: false,
+            disfluency_extra_words: Vec::new(),
+            redaction_enabled: false,
+            redaction_custom_patterns: Vec::new(),
+            redaction_audio_mode: default_redaction_audio_mode(),
+            word_timestamps: default_word_timestamps(),
+            dtw_timestamps: false,
+            low_confidence_threshold: default_low_confidence_threshold(),
+            stereo_diarize: false,
+            stereo_channel_names: Vec::new(),
+            speaker_clustering: false,
+            speaker_model_path: None,
+            speaker_model_input: default_speaker_model_input(),
+            speaker_count: 0,
+            speaker_similarity_threshold: default_speaker_similarity_threshold(),
+            job_keep_last: default_job_keep_last(),
+            job_max_age_days: default_job_max_age_days(),
+            job_max_total_mb: default_job_max_total_mb(),
+            temp_dir_path: None,
+            result_cache_enabled: default_result_cache_enabled(),
+            result_cache_max_mb: default_result_cache_max_mb(),
+        }
+    }
+}
+
+// 各枚举型设置的可选值
+const APP_LOCALES: &[&str] = &["zh-CN", "en"];
+const WHISPER_OPTIMIZATIONS: &[&str] = &["none", "vulkan", "coreml", "cuda"];
+const OUTPUT_SCRIPTS: &[&str] = &["keep", "zh-Hans", "zh-Hant", "zh-TW", "zh-HK"];
+const HALLUCINATION_FILTERS: &[&str] = &["off", "mark", "drop"];
+const REDACTION_AUDIO_MODES: &[&str] = &["beep", "silence"];
+const SPEAKER_MODEL_INPUTS: &[&str] = &["fbank", "waveform"];
+
+// 校验并规范化设置：路径统一写法，线程数限制在可用范围内，枚举值、阈值与规则必须合法。
+// lenient 用于读取：不合法的字段恢复为默认值并记为警告；保存时则作为错误拒绝
+fn normalize_settings(settings: &mut AppSettings, lenient: bool) -> validation::Report {
+    let messages = i18n::Messages::new(&settings.app_locale);
+    let defaults = AppSettings::default();
+    let mut report = validation::Report::default();
+
+    for path in [
+        &mut settings.whisper_models_path,
+        &mut settings.temp_dir_path,
+        &mut settings.speaker_model_path,
+        &mut settings.vad.model_path,
+    ] {
+        validation::normalize_optional_path(path);
+    }
+    if let Some(dir) = settings.whisper_models_path.as_deref().filter(|p| !Path::new(p).is_dir()) {
+        report.warn("whisper_models_path", "path_not_found", messages.format("settings.path_not_found", &[("value", &dir)]));
+    }
+    for (field, path) in [("speaker_model_path", &settings.speaker_model_path), ("vad.model_path", &settings.vad.model_path)] {
+        if let Some(file) = path.as_deref().filter(|p| !Path::new(p).is_file()) {
+            report.warn(field, "path_not_found", messages.format("settings.path_not_found", &[("value", &file)]));
+        }
+    }
+
+    // 线程数与处理器数不超过本机的逻辑核心数，0（自动）保持不变
+    let max_threads = cpu::logical_cores();
+    let threads = match settings.thread_count {
+        cpu::AUTO_THREADS => cpu::AUTO_THREADS,
+        n => n.clamp(1, max_threads),
+    };
+    if threads != settings.thread_count {
+        settings.thread_count = threads;
+        report.warn("thread_count", "clamped", messages.format("settings.clamped", &[("value", &threads)]));
+    }
+    let processors = settings.processor_count.clamp(1, max_threads);
+    if processors != settings.processor_count {
+        settings.processor_count = processors;
+        report.warn("processor_count", "clamped", messages.format("settings.clamped", &[("value", &processors)]));
+    }
+    if !validation::check_range(&mut report, lenient, &messages, "cpu_cap_percent", settings.cpu_cap_percent as f32, 0.0, 100.0)
+        && lenient
+    {
+        settings.cpu_cap_percent = defaults.cpu_cap_percent;
+    }
+
+    let choices: [(&str, &mut String, &[&str], String); 6] = [
+        ("app_locale", &mut settings.app_locale, APP_LOCALES, defaults.app_locale),
+        ("whisper_optimization", &mut settings.whisper_optimization, WHISPER_OPTIMIZATIONS, defaults.whisper_optimization),
+        ("output_script", &mut settings.output_script, OUTPUT_SCRIPTS, defaults.output_script),
+        ("hallucination_filter", &mut settings.hallucination_filter, HALLUCINATION_FILTERS, defaults.hallucination_filter),
+        ("redaction_audio_mode", &mut settings.redaction_audio_mode, REDACTION_AUDIO_MODES, defaults.redaction_audio_mode),
+        ("speaker_model_input", &mut settings.speaker_model_input, SPEAKER_MODEL_INPUTS, defaults.speaker_model_input),
+    ];
+    for (field, value, allowed, default) in choices {
+        if !validation::check_choice(&mut report, lenient, &messages, field, value, allowed) && lenient {
+            *value = default;
+        }
+    }
+    if settings.whisper_optimization == "coreml" && !cfg!(target_os = "macos") {
+        report.warn(
+            "whisper_optimization",
+            "unsupported_platform",
+            messages.format("settings.unsupported_platform", &[("value", &settings.whisper_optimization)]),
+        );
+    }
+
+    for (field, value, default) in [
+        ("whisper_language", &mut settings.whisper_language, defaults.whisper_language),
+        ("whisper_model", &mut settings.whisper_model, defaults.whisper_model),
+    ] {
+        *value = value.trim().to_string();
+        if value.is_empty() {
+            report.reject(lenient, &messages, field, "empty", messages.get("settings.empty"));
+            if lenient {
+                *value = default;
+            }
+        }
+    }
+
+    for (field, value, default) in [
+        ("low_confidence_threshold", &mut settings.low_confidence_threshold, defaults.low_confidence_threshold),
+        ("speaker_similarity_threshold", &mut settings.speaker_similarity_threshold, defaults.speaker_similarity_threshold),
+    ] {
+        if !validation::check_range(&mut report, lenient, &messages, field, *value, 0.0, 1.0) && lenient {
+            *value = default;
+        }
+    }
+
+    // 说话人聚类没有打包模型，必须指定声纹模型文件；未编译 diarization 功能的版本不能启用
+    if settings.speaker_clustering {
+        if !cfg!(feature = "diarization") {
+            report.reject(lenient, &messages, "speaker_clustering", "unsupported_build", messages.get("settings.diarization_unavailable"));
+        } else if settings.speaker_model_path.is_none() {
+            report.reject(lenient, &messages, "speaker_model_path", "empty", messages.get("settings.empty"));
+        }
+        if lenient && (!cfg!(feature = "diarization") || settings.speaker_model_path.is_none()) {
+            settings.speaker_clustering = false;
+        }
+    }
+
+    if let Err(e) = settings.vad.validate() {
+        report.reject(lenient, &messages, e.field(), e.code(), e.message(&messages));
+        if lenient {
+            settings.vad = vad::VadSettings { model_path: settings.vad.model_path.take(), ..defaults.vad };
+        }
+    }
+
+    // 自定义脱敏规则逐条检查，读取时只去掉无法编译的规则
+    let mut invalid_patterns = Vec::new();
+    for (index, pattern) in settings.redaction_custom_patterns.iter().enumerate() {
+        if redaction::Redactor::new(std::slice::from_ref(pattern)).is_err() {
+            invalid_patterns.push(pattern.clone());
+            report.reject(
+                lenient,
+                &messages,
+                "redaction_custom_patterns",
+                "invalid_value",
+                messages.format(
+                    "settings.redaction_pattern_invalid",
+                    &[("index", &(index + 1)), ("label", &pattern.label), ("pattern", &pattern.pattern)],
+                ),
+            );
         }
     }
+    if lenient {
+        settings.redaction_custom_patterns.retain(|p| !invalid_patterns.contains(p));
+    }
+
+    report
+}
+
+// 校验结果连同规范化后的设置一起返回，前端可直接用来刷新表单
+#[derive(Debug, Serialize)]
+struct SettingsCheck {
+    settings: AppSettings,
+    #[serde(flatten)]
+    report: validation::Report,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,6 +368,7 @@ struct ProcessResult {
     message: String,
     output_path: Option<String>,
     duration_seconds: Option<f64>, // 添加视频时长
+    job_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,13 +383,19 @@ struct ProgressInfo {
     current_seconds: f64,
     total_seconds: f64,
     percentage: f64,
+    // 预计剩余秒数，不含暂停的时间
+    eta_seconds: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 struct AppDataInfo {
     path: String,
     size_bytes: u64,
     size_formatted: String,
+    temp_path: String,
+    categories: Vec<storage::CategoryUsage>,
+    // 便携模式的数据目录与启用方式，未启用时为 null
+    portable: Option<paths::Portable>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -103,23 +411,218 @@ struct SystemInfo {
     tauri_version: String,
 }
 
-// 清理指定目录下的 .wav 临时文件
-fn cleanup_wav_files(dir_path: &std::path::Path) {
-    if let Ok(entries) = std::fs::read_dir(dir_path) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                // 递归清理子目录（等价于 temp/*/*.wav 等情况）
-                cleanup_wav_files(&path);
-            } else if path.is_file() {
-                if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                    if ext.eq_ignore_ascii_case("wav") {
-                        let _ = std::fs::remove_file(&path);
-                    }
-                }
-            }
+// 设置中指定临时目录时，实际使用其下的专用子目录，不与用户自己的文件混放
+const TEMP_SUBDIR: &str = "murmur-temp";
+
+// 临时目录：设置中指定位置下的 murmur-temp，否则为应用数据目录下的 temp
+fn resolve_temp_dir(app_dir: &Path, settings: &AppSettings) -> PathBuf {
+    match &settings.temp_dir_path {
+        Some(p) if !p.trim().is_empty() => PathBuf::from(p.trim()).join(TEMP_SUBDIR),
+        _ => app_dir.join("temp"),
+    }
+}
+
+// 统计范围包含默认 temp 目录，迁移临时目录后旧目录中的文件仍可清理；
+// 模型目录只有位于应用数据目录内时才计入
+fn storage_roots(app_dir: &Path, settings: &AppSettings) -> storage::StorageRoots {
+    let mut temp_dirs = vec![resolve_temp_dir(app_dir, settings)];
+    if temp_dirs[0] != app_dir.join("temp") {
+        temp_dirs.push(app_dir.join("temp"));
+    }
+    let models_dir = settings
+        .whisper_models_path
+        .as_ref()
+        .map(|p| PathBuf::from(p.trim().trim_matches('"')))
+        .filter(|p| p.starts_with(app_dir));
+    storage::StorageRoots {
+        app_dir: app_dir.to_path_buf(),
+        temp_dirs,
+        models_dir,
+        cache_dir: app_dir.join(cache::CACHE_DIR),
+    }
+}
+
+// 数据目录：便携模式下为可执行文件旁的目录，否则为系统的应用数据目录。
+// 设置、配置方案、缓存、默认的临时目录与模型目录都位于其中
+fn app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, MurmurError> {
+    if let Some(portable) = &app_handle.state::<paths::DataRoot>().0 {
+        return Ok(portable.dir.clone());
+    }
+    app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| MurmurError::AppDirUnavailable { detail: e.to_string() })
+}
+
+fn result_cache(app_handle: &tauri::AppHandle) -> Result<cache::ResultCache, MurmurError> {
+    let app_dir = app_data_dir(app_handle)?;
+    Ok(cache::ResultCache::new(app_dir.join(cache::CACHE_DIR)))
+}
+
+// 转换后的音频由输入内容与预处理参数（采样率、声道数）唯一确定
+fn conversion_key(input_hash: &str, channels: &str) -> String {
+    cache::key(&["audio", input_hash, "ar=16000", &format!("ac={}", channels)])
+}
+
+// 不影响识别结果的设置：路径、界面语言、硬件加速与线程、工作区与缓存策略
+const CACHE_IGNORED_SETTINGS: &[&str] = &[
+    "schema_version",
+    "whisper_models_path",
+    "app_locale",
+    "whisper_optimization",
+    "disable_gpu",
+    "thread_count",
+    "background_priority",
+    "cpu_cap_percent",
+    "job_keep_last",
+    "job_max_age_days",
+    "job_max_total_mb",
+    "temp_dir_path",
+    "result_cache_enabled",
+    "result_cache_max_mb",
+];
+
+// 不随配置方案切换的设置：本机路径、界面语言、工作区与缓存策略
+const PROFILE_EXCLUDED_SETTINGS: &[&str] = &[
+    "schema_version",
+    "whisper_models_path",
+    "app_locale",
+    "job_keep_last",
+    "job_max_age_days",
+    "job_max_total_mb",
+    "temp_dir_path",
+    "result_cache_enabled",
+    "result_cache_max_mb",
+];
+
+// 识别结果的缓存键：输入 + 生效设置 + 模型文件大小（同名模型被替换后缓存失效）
+fn result_key(input_key: &str, settings: &AppSettings, model_file: &Path) -> String {
+    let mut value = serde_json::to_value(settings).unwrap_or_default();
+    if let Some(map) = value.as_object_mut() {
+        for name in CACHE_IGNORED_SETTINGS {
+            map.remove(*name);
+        }
+    }
+    let model_size = std::fs::metadata(model_file).map(|m| m.len()).unwrap_or(0);
+    cache::key(&["result", input_key, &cache::canonical_json(&value), &model_size.to_string()])
+}
+
+// 识别结果在缓存中的文件名，即相对音频路径的后缀
+const RESULT_FILES: &[&str] = &[
+    "transcript.json",
+    "json",
+    "srt",
+    "lrc",
+    "txt",
+    "verbatim.transcript.json",
+    "verbatim.srt",
+    "verbatim.lrc",
+    "verbatim.txt",
+];
+
+// 删除某个音频对应的全部识别结果文件，返回删除的数量
+fn remove_result_files(audio_file_path: &str) -> usize {
+    RESULT_FILES
+        .iter()
+        .map(|name| transcript::output_path(audio_file_path, name))
+        .filter(|path| std::fs::remove_file(path).is_ok())
+        .count()
+}
+
+// 写入缓存失败只记录日志，不影响本次处理
+fn store_in_cache(
+    app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+    key: &str,
+    kind: &str,
+    source: &str,
+    files: &[(PathBuf, String)],
+    log_path: &Path,
+) {
+    let stored = result_cache(app_handle).and_then(|c| {
+        c.store(key, kind, source, files, workspace::now_secs())?;
+        if settings.result_cache_max_mb > 0 {
+            c.enforce_limit(settings.result_cache_max_mb * 1024 * 1024, key);
+        }
+        Ok(())
+    });
+    match stored {
+        Ok(()) => append_log_line(log_path, "cache", &format!("已写入缓存: {} {}", kind, key)),
+        Err(e) => append_log_line(log_path, "cache", &format!("写入缓存失败: {}", e)),
+    }
+}
+
+// 命中缓存时把文件复制到任务目录，返回是否成功
+fn restore_from_cache(
+    app_handle: &tauri::AppHandle,
+    key: &str,
+    target: impl Fn(&str) -> PathBuf,
+    log_path: &Path,
+) -> bool {
+    let Ok(cache) = result_cache(app_handle) else {
+        return false;
+    };
+    let Some((dir, entry)) = cache.lookup(key, workspace::now_secs()) else {
+        return false;
+    };
+    let targets: Vec<(String, PathBuf)> = entry.files.iter().map(|f| (f.clone(), target(f))).collect();
+    match cache::ResultCache::restore(&dir, &targets) {
+        Ok(()) => {
+            append_log_line(log_path, "cache", &format!("命中缓存: {} {}（来源 {}）", entry.kind, key, entry.source));
+            true
         }
+        Err(e) => {
+            append_log_line(log_path, "cache", &format!("恢复缓存失败: {}", e));
+            false
+        }
+    }
+}
+
+fn retention_policy(settings: &AppSettings) -> workspace::RetentionPolicy {
+    workspace::RetentionPolicy {
+        keep_last: settings.job_keep_last as usize,
+        max_age_days: settings.job_max_age_days as u64,
+        max_total_bytes: settings.job_max_total_mb * 1024 * 1024,
+    }
+}
+
+// 其他应用实例创建的任务：其进程仍存活时视为使用中
+fn process_alive(pid: u32) -> bool {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, ProcessRefreshKind::new());
+    sys.process(pid).is_some()
+}
+
+// 新建任务工作区：先按保留规则回收旧任务，再创建任务目录并登记引用
+fn prepare_job(
+    app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+    source: &str,
+    input_key: Option<String>,
+) -> Result<(workspace::JobMeta, PathBuf), MurmurError> {
+    let app_dir = app_data_dir(app_handle)?;
+    let temp_dir = resolve_temp_dir(&app_dir, settings);
+    let registry = app_handle.state::<workspace::JobRegistry>();
+    let report = workspace::cleanup(
+        &temp_dir,
+        Some(&app_dir.join("temp")),
+        retention_policy(settings),
+        &registry,
+        &process_alive,
+        false,
+    );
+    if !report.removed.is_empty() {
+        app_log(
+            &app_dir,
+            "cleanup",
+            &format!("已清理 {} 个旧任务，释放 {}", report.removed.len(), format_file_size(report.freed_bytes)),
+        );
     }
+    let (meta, dir) = workspace::create_job(&temp_dir, source, input_key).map_err(|e| MurmurError::io(&temp_dir, e))?;
+    registry.acquire(&meta.id);
+    Ok((meta, dir))
 }
 
 // 日志工具：时间戳（毫秒）+ 追加写入
@@ -140,17 +643,97 @@ fn append_log_line<P: AsRef<Path>>(log_path: P, tag: &str, line: &str) {
         .and_then(|mut f| f.write_all(buf.as_bytes()));
 }
 
-// 全局保存正在运行的 whisper 进程句柄，便于停止
+// 应用级日志：不属于某个任务的事件写入应用数据目录下的 murmur.log
+fn app_log(app_dir: &Path, tag: &str, line: &str) {
+    append_log_line(app_dir.join(storage::APP_LOG_FILE), tag, line);
+}
+
+// 全局保存正在运行的 whisper 进程句柄，便于停止、暂停与恢复
 struct WhisperProcState {
     child: tokio::sync::Mutex<Option<CommandChild>>,
+    // 当前识别进程的优先级与暂停状态，未在识别时为 None
+    control: std::sync::Mutex<Option<priority::JobControl>>,
 }
 
 impl Default for WhisperProcState {
     fn default() -> Self {
         Self {
             child: tokio::sync::Mutex::new(None),
+            control: std::sync::Mutex::new(None),
+        }
+    }
+}
+
+// 广播识别进程的调度状态，None 表示识别已结束
+fn publish_whisper_status(app_handle: &tauri::AppHandle, status: Option<priority::ThrottleStatus>) {
+    let _ = app_handle.emit("whisper-status", status);
+}
+
+// 在当前识别进程的暂停状态上执行操作，未在识别时返回 None
+fn with_job_control<T>(app_handle: &tauri::AppHandle, f: impl FnOnce(&mut priority::JobControl) -> T) -> Option<T> {
+    let state = app_handle.state::<WhisperProcState>();
+    let mut guard = state.control.lock().ok()?;
+    guard.as_mut().map(f)
+}
+
+// 手动暂停或恢复当前识别，自动暂停（CPU 上限）仍生效时恢复后进程继续保持挂起
+fn set_job_paused(app_handle: &tauri::AppHandle, paused: bool) -> Result<priority::ThrottleStatus, MurmurError> {
+    let status = with_job_control(app_handle, |control| control.set_user_paused(paused).map(|()| control.snapshot()))
+        .ok_or(MurmurError::WhisperNotRunning)?
+        .map_err(|e| MurmurError::WhisperControl { detail: e.to_string() })?;
+    let _ = app_handle.emit(if paused { "whisper-paused" } else { "whisper-resumed" }, &status);
+    publish_whisper_status(app_handle, Some(status.clone()));
+    Ok(status)
+}
+
+// 一次性运行的 sidecar（ffmpeg）的输出
+struct SidecarOutput {
+    code: Option<i32>,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+impl SidecarOutput {
+    fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+// 运行 sidecar 并收集输出；background 时降低子进程的调度优先级，结果写入任务日志
+async fn run_sidecar(
+    command: tauri_plugin_shell::process::Command,
+    background: bool,
+    log_path: &Path,
+) -> Result<SidecarOutput, String> {
+    use tauri_plugin_shell::process::CommandEvent;
+
+    let (mut rx, child) = command.spawn().map_err(|e| e.to_string())?;
+    if background {
+        match priority::lower_priority(child.pid()) {
+            Ok(()) => append_log_line(log_path, "priority", "已降低子进程的优先级"),
+            Err(e) => append_log_line(log_path, "priority", &format!("降低子进程的优先级失败: {}", e)),
         }
     }
+    let mut output = SidecarOutput { code: None, stdout: Vec::new(), stderr: Vec::new() };
+    let push_line = |buf: &mut Vec<u8>, mut line: Vec<u8>| {
+        if !line.ends_with(b"\n") {
+            line.push(b'\n');
+        }
+        buf.extend(line);
+    };
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => push_line(&mut output.stdout, line),
+            CommandEvent::Stderr(line) => push_line(&mut output.stderr, line),
+            CommandEvent::Terminated(payload) => {
+                output.code = payload.code;
+                break;
+            }
+            CommandEvent::Error(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(output)
 }
 
 // 简单格式化命令行为字符串，便于日志打印
@@ -254,40 +837,185 @@ fn extract_percentage_from_progress_line(line: &str) -> Option<f64> {
     num_str.trim().parse::<f64>().ok()
 }
 
+// 自动检测语言时 whisper 在 stderr 输出 "auto-detected language: ja (p = 0.97)"
+fn extract_detected_language(line: &str) -> Option<&str> {
+    let rest = line.split("auto-detected language:").nth(1)?;
+    rest.split_whitespace().next()
+}
+
 // 已移除：原先通过 ffmpeg 探测媒体时长的逻辑，改为仅依赖 Whisper 的进度百分比。
 
+// 识别完成后的后处理：读取 whisper 的 JSON 输出，按设置转换字形，
+// 保存转写结果并重新生成 SRT/LRC/TXT，保证各导出格式内容一致
+fn finalize_transcript(
+    audio_file_path: &str,
+    settings: &AppSettings,
+    speaker_model: Option<&Path>,
+    log_path: &Path,
+) -> Result<transcript::Transcript, String> {
+    let json_path = transcript::output_path(audio_file_path, "json");
+    let content = transcript::read_whisper_json(&json_path)?;
+    let mut result = transcript::parse_whisper_json(&content)?;
+    let detected_language = result.language.clone();
+    let language = detected_language
+        .as_deref()
+        .or(Some(settings.whisper_language.as_str()).filter(|l| *l != "auto"));
+
+    if let Some(target) = opencc::ScriptTarget::from_setting(&settings.output_script) {
+        if language.is_some_and(opencc::applies_to) {
+            for seg in result.segments.iter_mut() {
+                seg.text = opencc::convert(&seg.text, target);
+                for w in seg.words.iter_mut() {
+                    w.text = opencc::convert(&w.text, target);
+                }
+            }
+            append_log_line(log_path, "postprocess", &format!("字形转换: {}", settings.output_script));
+        } else {
+            append_log_line(log_path, "postprocess", &format!("字形转换: 跳过（语言 {}）", language.unwrap_or("未知")));
+        }
+    }
+
+    // 幻觉检测：结合音频能量判断静音段落，汇总写入日志
+    if let Some(mode) = hallucination::FilterMode::from_setting(&settings.hallucination_filter) {
+        let energy = match audio::compute_energy(Path::new(audio_file_path), 50) {
+            Ok(e) => Some(e),
+            Err(e) => {
+                append_log_line(log_path, "postprocess", &format!("读取音频能量失败: {}", e));
+                None
+            }
+        };
+        let summary = hallucination::analyze(&mut result, energy.as_ref(), language, mode);
+        append_log_line(log_path, "postprocess", &summary.describe());
+    }
+
+    // 低置信度词：在幻觉检测之后标记，避免 drop 模式把这些段落一并删除
+    if result.segments.iter().any(|s| !s.words.is_empty()) {
+        let flagged = transcript::flag_low_confidence(&mut result, settings.low_confidence_threshold);
+        append_log_line(log_path, "postprocess", &format!("低置信度: 标记 {} 段", flagged));
+    }
+
+    // 说话人聚类：失败时只记录日志，不影响转写结果
+    if let Some(model_path) = speaker_model {
+        let options = diarization::DiarizationOptions {
+            num_speakers: Some(settings.speaker_count as usize).filter(|n| *n > 0),
+            threshold: settings.speaker_similarity_threshold,
+            input: diarization::ModelInput::from_setting(&settings.speaker_model_input),
+        };
+        match diarization::diarize(&mut result, Path::new(audio_file_path), model_path, &options) {
+            Ok(count) => append_log_line(log_path, "postprocess", &format!("说话人聚类: {} 人", count)),
+            Err(e) => append_log_line(log_path, "postprocess", &format!("说话人聚类失败: {}", e)),
+        }
+    }
+
+    // 未命名的说话人按界面语言命名；声道说话人使用用户设置的名称，例如 坐席/客户
+    result.default_speaker_names(&i18n::Messages::new(&settings.app_locale));
+    if settings.stereo_diarize {
+        result.name_speakers(&settings.stereo_channel_names);
+    }
+
+    // 敏感信息脱敏：在保存任何结果之前完成，逐字稿同样不保留原文
+    if settings.redaction_enabled {
+        let redactor = redaction::Redactor::new(&settings.redaction_custom_patterns).map_err(|e| e.to_string())?;
+        let count = redaction::apply(&mut result, &redactor);
+        // whisper 原始 JSON 含有未脱敏文本，解析后删除
+        if let Err(e) = std::fs::remove_file(&json_path) {
+            append_log_line(log_path, "postprocess", &format!("删除 whisper JSON 失败: {}", e));
+        }
+        append_log_line(log_path, "postprocess", &format!("敏感信息脱敏: 替换 {} 处", count));
+    }
+
+    // 口语清理：先保存逐字稿，清理版与逐字稿都可以导出
+    if settings.disfluency_filter {
+        let verbatim_base = transcript::verbatim_base(audio_file_path);
+        transcript::save_transcript(&transcript::transcript_path(&verbatim_base), &result).map_err(|e| e.to_string())?;
+        transcript::write_exports(&verbatim_base, &result).map_err(|e| e.to_string())?;
+        let filter = disfluency::DisfluencyFilter::new(language, &settings.disfluency_extra_words);
+        let dropped = disfluency::apply(&mut result, &filter);
+        append_log_line(log_path, "postprocess", &format!("口语清理: 删除空段 {} 个", dropped));
+    }
+
+    transcript::save_transcript(&transcript::transcript_path(audio_file_path), &result).map_err(|e| e.to_string())?;
+    transcript::write_exports(audio_file_path, &result).map_err(|e| e.to_string())?;
+    append_log_line(log_path, "postprocess", &format!("已生成转写结果: {} 段", result.segments.len()));
+    Ok(result)
+}
+
+fn is_tinydiarize_model(model_file_name: &str) -> bool {
+    model_file_name.contains("tdrz")
+}
+
+// 由模型文件名推断 whisper.cpp 的 DTW 预设，例如
+// ggml-large-v3-turbo-q5_0.bin -> large.v3.turbo，ggml-base.en.bin -> base.en
+fn dtw_preset(model_file_name: &str) -> Option<String> {
+    let stem = model_file_name.strip_prefix("ggml-")?.strip_suffix(".bin")?;
+    // 去掉量化后缀（-q5_0、-q8_0 等）
+    let stem = match stem.rsplit_once("-q") {
+        Some((base, quant)) if quant.contains('_') => base,
+        _ => stem,
+    };
+    let preset = stem.replace('-', ".");
+    const PRESETS: &[&str] = &[
+        "tiny", "tiny.en", "base", "base.en", "small", "small.en", "medium", "medium.en",
+        "large.v1", "large.v2", "large.v3", "large.v3.turbo",
+    ];
+    PRESETS.contains(&preset.as_str()).then_some(preset)
+}
+
 #[tauri::command]
-async fn get_app_data_info(app_handle: tauri::AppHandle) -> Result<AppDataInfo, String> {
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("获取应用目录失败: {}", e))?;
+async fn get_app_data_info(app_handle: tauri::AppHandle) -> Result<AppDataInfo, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
     
     // 如果目录不存在，创建它
     if !app_dir.exists() {
         std::fs::create_dir_all(&app_dir)
-            .map_err(|e| format!("创建应用目录失败: {}", e))?;
+            .map_err(|e| MurmurError::io(&app_dir, e))?;
     }
     
-    let size_bytes = calculate_directory_size(&app_dir);
+    let settings = load_settings(app_handle.clone()).await?;
+    let roots = storage_roots(&app_dir, &settings);
+    let categories = storage::breakdown(&roots);
+    let size_bytes = categories.iter().map(|c| c.size_bytes).sum();
     let size_formatted = format_file_size(size_bytes);
     
     Ok(AppDataInfo {
         path: app_dir.to_string_lossy().to_string(),
         size_bytes,
         size_formatted,
+        temp_path: roots.temp_dirs[0].to_string_lossy().to_string(),
+        categories,
+        portable: app_handle.state::<paths::DataRoot>().0.clone(),
     })
 }
 
+// 按类别清理应用数据，dry_run 时只返回将被删除的文件；正在使用的任务不受影响
+#[tauri::command]
+async fn clean_app_data(
+    app_handle: tauri::AppHandle,
+    categories: Vec<storage::Category>,
+    dry_run: Option<bool>,
+) -> Result<storage::CleanReport, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let settings = load_settings(app_handle.clone()).await?;
+    let roots = storage_roots(&app_dir, &settings);
+    let registry = app_handle.state::<workspace::JobRegistry>();
+    let protected: Vec<PathBuf> = roots
+        .temp_dirs
+        .iter()
+        .flat_map(|t| workspace::active_job_dirs(t, &registry, &process_alive))
+        .collect();
+    Ok(storage::clean(&roots, &categories, &protected, dry_run.unwrap_or(false)))
+}
+
 #[tauri::command]
-async fn open_app_data_directory(app_handle: tauri::AppHandle) -> Result<(), String> {
+async fn open_app_data_directory(app_handle: tauri::AppHandle) -> Result<(), MurmurError> {
     use tauri_plugin_shell::ShellExt;
     
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("获取应用目录失败: {}", e))?;
+    let app_dir = app_data_dir(&app_handle)?;
     
     // 如果目录不存在，创建它
     if !app_dir.exists() {
         std::fs::create_dir_all(&app_dir)
-            .map_err(|e| format!("创建应用目录失败: {}", e))?;
+            .map_err(|e| MurmurError::io(&app_dir, e))?;
     }
     
     // 在 macOS 上使用 open 命令打开 Finder
@@ -309,21 +1037,31 @@ async fn open_app_data_directory(app_handle: tauri::AppHandle) -> Result<(), Str
                 Ok(())
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                Err(format!("打开目录失败: {}", stderr))
+                Err(MurmurError::io(&app_dir, stderr))
             }
         }
-        Err(e) => Err(format!("执行打开命令失败: {}", e))
+        Err(e) => Err(MurmurError::io(&app_dir, e))
+    }
+}
+
+// 按当前设置的界面语言取后端文案；设置读取失败时使用默认语言
+async fn load_messages(app_handle: &tauri::AppHandle) -> i18n::Messages {
+    match load_settings(app_handle.clone()).await {
+        Ok(settings) => i18n::Messages::new(&settings.app_locale),
+        Err(_) => i18n::Messages::default(),
     }
 }
 
 // 获取 FFmpeg 版本
-async fn get_ffmpeg_version(app_handle: &tauri::AppHandle) -> String {
+async fn get_ffmpeg_version(app_handle: &tauri::AppHandle, messages: &i18n::Messages) -> String {
     use tauri_plugin_shell::ShellExt;
     
     match app_handle.shell().sidecar("ffmpeg") {
         Ok(cmd) => {
             let args = vec!["-version".to_string()];
-            println!("执行命令: {}", format_cmd_with_args("ffmpeg", &args));
+            if let Ok(app_dir) = app_data_dir(app_handle) {
+                app_log(&app_dir, "CMD", &format_cmd_with_args("ffmpeg", &args));
+            }
             match cmd.args(&args).output().await {
                 Ok(output) => {
                     if output.status.success() {
@@ -339,11 +1077,11 @@ async fn get_ffmpeg_version(app_handle: &tauri::AppHandle) -> String {
         }
         Err(_) => {}
     }
-    "FFmpeg 不可用".to_string()
+    messages.get("system.ffmpeg_unavailable")
 }
 
 // 获取系统信息
-fn get_system_info() -> (String, String, String, String, String, String) {
+fn get_system_info(messages: &i18n::Messages) -> (String, String, String, String, String, String) {
     use sysinfo::System;
     
     let mut sys = System::new_all();
@@ -357,19 +1095,19 @@ fn get_system_info() -> (String, String, String, String, String, String) {
     } else if cfg!(target_os = "linux") {
         "Linux".to_string()
     } else {
-        "Unknown".to_string()
+        messages.get("system.unknown_os")
     };
     
-    let os_version = System::os_version().unwrap_or_else(|| "未知版本".to_string());
+    let os_version = System::os_version().unwrap_or_else(|| messages.get("system.unknown_version"));
     
     // CPU 信息
     let cpu_brand = if let Some(cpu) = sys.cpus().first() {
         cpu.brand().to_string()
     } else {
-        "未知 CPU".to_string()
+        messages.get("system.unknown_cpu")
     };
     
-    let cpu_cores = format!("{} 核心", sys.cpus().len());
+    let cpu_cores = messages.format("system.cpu_cores", &[("count", &sys.cpus().len())]);
     
     // 内存信息
     let total_memory = {
@@ -380,19 +1118,34 @@ fn get_system_info() -> (String, String, String, String, String, String) {
     
     // GPU 信息（简化版本，在 macOS 上获取基本信息）
     let gpu_info = if cfg!(target_os = "macos") {
-        "Apple GPU (通过系统检测)".to_string()
+        messages.get("system.gpu_apple")
     } else {
-        "GPU 信息需要额外检测".to_string()
+        messages.get("system.gpu_unknown")
     };
     
     (os_type, os_version, cpu_brand, cpu_cores, total_memory, gpu_info)
 }
 
+#[derive(Debug, Serialize)]
+struct ThreadInfo {
+    cpu: cpu::CpuInfo,
+    // 按当前设置下一次识别将使用的线程与处理器数
+    plan: cpu::ThreadPlan,
+}
+
+#[tauri::command]
+async fn get_thread_info(app_handle: tauri::AppHandle) -> Result<ThreadInfo, MurmurError> {
+    let settings = load_settings(app_handle).await?;
+    let cpu = cpu::CpuInfo::detect();
+    Ok(ThreadInfo { cpu, plan: cpu::plan(settings.thread_count, settings.processor_count, &cpu) })
+}
+
 #[tauri::command]
-async fn get_system_info_command(app_handle: tauri::AppHandle) -> Result<SystemInfo, String> {
-    let (os_type, os_version, cpu_brand, cpu_cores, total_memory, gpu_info) = get_system_info();
+async fn get_system_info_command(app_handle: tauri::AppHandle) -> Result<SystemInfo, MurmurError> {
+    let messages = load_messages(&app_handle).await;
+    let (os_type, os_version, cpu_brand, cpu_cores, total_memory, gpu_info) = get_system_info(&messages);
     
-    let ffmpeg_version = get_ffmpeg_version(&app_handle).await;
+    let ffmpeg_version = get_ffmpeg_version(&app_handle, &messages).await;
     
     // 从 Cargo.toml 获取应用版本
     let app_version = env!("CARGO_PKG_VERSION").to_string();
@@ -422,7 +1175,8 @@ struct VulkanInfo {
 }
 
 #[tauri::command]
-async fn get_vulkan_support() -> Result<VulkanInfo, String> {
+async fn get_vulkan_support(app_handle: tauri::AppHandle) -> Result<VulkanInfo, MurmurError> {
+    let messages = load_messages(&app_handle).await;
     // 动态加载 Vulkan 运行库
     let entry = unsafe { ash::Entry::load() };
     let entry = match entry {
@@ -432,7 +1186,7 @@ async fn get_vulkan_support() -> Result<VulkanInfo, String> {
                 supported: false,
                 api_version: None,
                 device_count: 0,
-                error: Some(format!("加载 Vulkan 失败: {}", e)),
+                error: Some(messages.format("vulkan.load_failed", &[("error", &e)])),
             })
         }
     };
@@ -469,7 +1223,7 @@ async fn get_vulkan_support() -> Result<VulkanInfo, String> {
                 supported: false,
                 api_version,
                 device_count: 0,
-                error: Some(format!("创建 Vulkan 实例失败: {}", e)),
+                error: Some(messages.format("vulkan.instance_failed", &[("error", &e)])),
             })
         }
     };
@@ -478,7 +1232,7 @@ async fn get_vulkan_support() -> Result<VulkanInfo, String> {
     let devices = unsafe { instance.enumerate_physical_devices() };
     let (device_count, supported, error) = match devices {
         Ok(list) => (list.len() as u32, !list.is_empty(), None),
-        Err(e) => (0, false, Some(format!("枚举物理设备失败: {}", e))),
+        Err(e) => (0, false, Some(messages.format("vulkan.enumerate_failed", &[("error", &e)]))),
     };
 
     // 清理实例
@@ -499,38 +1253,20 @@ async fn process_media_file(
     app_handle: tauri::AppHandle,
     file_data: Vec<u8>,
     file_name: String,
-) -> Result<ProcessResult, String> {
+    force_rerun: Option<bool>,
+) -> Result<ProcessResult, MurmurError> {
     use tauri_plugin_shell::ShellExt;
     
-    // 获取应用程序目录
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app directory: {}", e))?;
-    
-    // 创建 temp 目录
-    let temp_dir = app_dir.join("temp");
-    if !temp_dir.exists() {
-        std::fs::create_dir_all(&temp_dir)
-            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    }
-    // 清理旧的 .wav 文件
-    cleanup_wav_files(&temp_dir);
-    
-    // 为本次处理创建独立时间戳目录
-    let ts = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-    let run_dir = temp_dir.join(format!("{}", ts));
-    if !run_dir.exists() {
-        std::fs::create_dir_all(&run_dir)
-            .map_err(|e| format!("Failed to create run directory: {}", e))?;
-    }
-
-    // 保存输入文件到本次处理目录
-    let input_path = run_dir.join(&file_name);
-    std::fs::write(&input_path, &file_data)
-        .map_err(|e| format!("Failed to save input file: {}", e))?;
-    
+    // 立体声分声道识别说话人时保留两个声道
+    let settings = load_settings(app_handle.clone()).await?;
+    let messages = i18n::Messages::new(&settings.app_locale);
+    let channel_count: u16 = if settings.stereo_diarize { 2 } else { 1 };
+    let channels = channel_count.to_string();
+
+    // 为本次处理创建独立的任务目录
+    let input_key = conversion_key(&cache::hash_bytes(&file_data), &channels);
+    let (job, run_dir) = prepare_job(&app_handle, &settings, &file_name, Some(input_key.clone()))?;
+
     // 生成输出文件名（转换为 .wav）
     let file_stem = std::path::Path::new(&file_name)
         .file_stem()
@@ -542,7 +1278,50 @@ async fn process_media_file(
     
     // 获取视频时长
     let duration: Option<f64> = None; // 不再探测媒体时长
-    
+
+    // 同一输入已转换过时直接复用缓存的音频
+    if settings.result_cache_enabled && !force_rerun.unwrap_or(false)
+        && restore_from_cache(&app_handle, &input_key, |_| output_path.clone(), &log_path)
+    {
+        return Ok(ProcessResult {
+            success: true,
+            message: messages.get("process.cached_audio"),
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            duration_seconds: duration,
+            job_id: Some(job.id),
+        });
+    }
+
+    // 保存输入文件到本次处理目录；加前缀避免与同名的 wav 输出冲突
+    let input_path = run_dir.join(format!("source-{}", file_name));
+    std::fs::write(&input_path, &file_data)
+        .map_err(|e| {
+            app_handle.state::<workspace::JobRegistry>().release(&job.id);
+            MurmurError::io(&input_path, e)
+        })?;
+
+    // 已是 16 kHz 16 位 PCM 的 wav 无需转换，直接改名为输出文件
+    if audio::read_wav_info(&input_path).is_ok_and(|info| info.is_whisper_ready(channel_count)) {
+        std::fs::rename(&input_path, &output_path)
+            .map_err(|e| MurmurError::io(&output_path, e))
+            .and_then(|_| {
+                audio::validate_for_whisper(&output_path, channel_count)
+                    .map_err(MurmurError::from)
+            })
+            .map_err(|e| {
+                app_handle.state::<workspace::JobRegistry>().release(&job.id);
+                e
+            })?;
+        append_log_line(&log_path, "convert", "输入已是 16 kHz PCM WAV，跳过转换");
+        return Ok(ProcessResult {
+            success: true,
+            message: messages.get("process.no_conversion"),
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            duration_seconds: duration,
+            job_id: Some(job.id),
+        });
+    }
+
     // 构建 ffmpeg 命令
     let args = vec![
         "-i".to_string(),
@@ -550,20 +1329,22 @@ async fn process_media_file(
         "-ar".to_string(),
         "16000".to_string(),
         "-ac".to_string(),
-        "1".to_string(),
+        channels.clone(),
         output_path.to_string_lossy().to_string(),
         "-y".to_string(), // 覆盖输出文件
     ];
     
     // 使用打包的 ffmpeg
     let ffmpeg_sidecar = app_handle.shell().sidecar("ffmpeg")
-        .map_err(|e| format!("无法获取 ffmpeg sidecar: {}", e))?;
+        .map_err(|e| {
+            app_handle.state::<workspace::JobRegistry>().release(&job.id);
+            MurmurError::SidecarUnavailable { name: "ffmpeg".to_string(), detail: e.to_string() }
+        })?;
     
     // 执行 ffmpeg 命令
     let cmd_str = format_cmd_with_args("ffmpeg", &args);
-    println!("执行命令: {}", cmd_str);
     append_log_line(&log_path, "CMD", &cmd_str);
-    let ffmpeg_result = ffmpeg_sidecar.args(&args).output().await;
+    let ffmpeg_result = run_sidecar(ffmpeg_sidecar.args(&args), settings.background_priority, &log_path).await;
     
     // 删除临时输入文件
     let _ = std::fs::remove_file(&input_path);
@@ -576,36 +1357,51 @@ async fn process_media_file(
             let err_str = String::from_utf8_lossy(&output.stderr);
             for l in err_str.lines() { append_log_line(&log_path, "ffmpeg:stderr", l); }
 
-            if output.status.success() {
+            if output.success() {
+                // ffmpeg 成功退出也可能产生空文件或全静音的结果
+                if let Err(e) = audio::validate_for_whisper(&output_path, channel_count) {
+                    append_log_line(&log_path, "convert", &format!("转换结果无效: {}", e));
+                    app_handle.state::<workspace::JobRegistry>().release(&job.id);
+                    return Err(e.into());
+                }
+                if settings.result_cache_enabled {
+                    let files = [(output_path.clone(), "audio.wav".to_string())];
+                    store_in_cache(&app_handle, &settings, &input_key, "audio", &file_name, &files, &log_path);
+                }
                 Ok(ProcessResult {
                     success: true,
-                    message: "文件转换成功，准备开始语音识别...".to_string(),
+                    message: messages.get("process.converted"),
                     output_path: Some(output_path.to_string_lossy().to_string()),
                     duration_seconds: duration,
+                    job_id: Some(job.id),
                 })
             } else {
+                // 转换失败的任务不再被引用，留给保留规则回收
+                app_handle.state::<workspace::JobRegistry>().release(&job.id);
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                Err(format!("FFmpeg 执行失败: {}", stderr))
+                Err(MurmurError::FfmpegFailed { detail: stderr.to_string() })
             }
         }
         Err(e) => {
-            Err(format!("无法执行 ffmpeg 命令: {}", e))
+            app_handle.state::<workspace::JobRegistry>().release(&job.id);
+            Err(MurmurError::FfmpegSpawn { detail: e.to_string() })
         }
     }
 }
 
 #[tauri::command]
-async fn select_directory(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+async fn select_directory(app_handle: tauri::AppHandle) -> Result<Option<String>, MurmurError> {
     use tauri_plugin_dialog::{DialogExt, FilePath};
     use tokio::sync::oneshot;
 
+    let messages = load_messages(&app_handle).await;
     // 使用 Tauri v2 对话框插件，跨平台选择目录（Windows/macOS/Linux）
     let (tx, rx) = oneshot::channel::<Option<String>>();
 
     app_handle
         .dialog()
         .file()
-        .set_title("选择目录")
+        .set_title(messages.get("dialog.select_directory"))
         .pick_folder(move |folder| {
             let selected = folder.and_then(|fp| match fp {
                 FilePath::Path(p) => Some(p.to_string_lossy().to_string()),
@@ -627,22 +1423,23 @@ async fn select_directory(app_handle: tauri::AppHandle) -> Result<Option<String>
 
     match rx.await {
         Ok(opt) => Ok(opt),        // Some(path) 或 None（用户取消）
-        Err(e) => Err(format!("选择目录失败: {}", e)),
+        Err(e) => Err(MurmurError::Dialog { detail: e.to_string() }),
     }
 }
 
 #[tauri::command]
-async fn select_media_file(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+async fn select_media_file(app_handle: tauri::AppHandle) -> Result<Option<String>, MurmurError> {
     use tauri_plugin_dialog::{DialogExt, FilePath};
     use tokio::sync::oneshot;
 
+    let messages = load_messages(&app_handle).await;
     let (tx, rx) = oneshot::channel::<Option<String>>();
 
     let mut builder = app_handle.dialog().file();
-    builder = builder.set_title("选择视频或音频文件");
+    builder = builder.set_title(messages.get("dialog.select_media"));
     // 常见媒体扩展名
     builder = builder.add_filter(
-        "媒体文件",
+        messages.get("dialog.media_filter"),
         &["mp4", "mov", "mkv", "avi", "webm", "m4v", "mp3", "wav", "m4a", "flac", "aac", "ogg", "opus"],
     );
 
@@ -664,7 +1461,7 @@ async fn select_media_file(app_handle: tauri::AppHandle) -> Result<Option<String
 
     match rx.await {
         Ok(opt) => Ok(opt),
-        Err(e) => Err(format!("选择文件失败: {}", e)),
+        Err(e) => Err(MurmurError::Dialog { detail: e.to_string() }),
     }
 }
 
@@ -693,14 +1490,14 @@ fn guess_media_kind(path: &std::path::Path) -> String {
 }
 
 #[tauri::command]
-async fn get_file_info(_app_handle: tauri::AppHandle, path: String) -> Result<FileInfo, String> {
+async fn get_file_info(_app_handle: tauri::AppHandle, path: String) -> Result<FileInfo, MurmurError> {
     let p = std::path::Path::new(&path);
     let name = p
         .file_name()
         .and_then(|s| s.to_str())
-        .ok_or("无效的文件路径")?
+        .ok_or_else(|| MurmurError::InvalidPath { path: path.clone() })?
         .to_string();
-    let meta = std::fs::metadata(&p).map_err(|e| format!("读取文件信息失败: {}", e))?;
+    let meta = std::fs::metadata(p).map_err(|e| MurmurError::io(p, e))?;
     let size = meta.len();
     let kind = guess_media_kind(&p);
     Ok(FileInfo { name, size, kind })
@@ -710,34 +1507,24 @@ async fn get_file_info(_app_handle: tauri::AppHandle, path: String) -> Result<Fi
 async fn process_media_file_from_path(
     app_handle: tauri::AppHandle,
     input_path: String,
-) -> Result<ProcessResult, String> {
+    force_rerun: Option<bool>,
+) -> Result<ProcessResult, MurmurError> {
     use tauri_plugin_shell::ShellExt;
 
-    // 获取应用程序目录
-    let app_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app directory: {}", e))?;
-
-    // 创建 temp 目录
-    let temp_dir = app_dir.join("temp");
-    if !temp_dir.exists() {
-        std::fs::create_dir_all(&temp_dir)
-            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    }
-    // 清理旧的 .wav 文件
-    cleanup_wav_files(&temp_dir);
-
-    // 为本次处理创建独立时间戳目录
-    let ts = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-    let run_dir = temp_dir.join(format!("{}", ts));
-    if !run_dir.exists() {
-        std::fs::create_dir_all(&run_dir)
-            .map_err(|e| format!("Failed to create run directory: {}", e))?;
-    }
+    // 立体声分声道识别说话人时保留两个声道
+    let settings = load_settings(app_handle.clone()).await?;
+    let messages = i18n::Messages::new(&settings.app_locale);
+    let channel_count: u16 = if settings.stereo_diarize { 2 } else { 1 };
+    let channels = channel_count.to_string();
+
+    // 为本次处理创建独立的任务目录；大文件的哈希在阻塞线程中计算
+    let hash_path = PathBuf::from(&input_path);
+    let input_hash = tokio::task::spawn_blocking(move || cache::hash_file(&hash_path))
+        .await
+        .map_err(|e| MurmurError::HashFailed { path: input_path.clone(), detail: e.to_string() })?
+        .map_err(|e| MurmurError::io(&input_path, e))?;
+    let input_key = conversion_key(&input_hash, &channels);
+    let (job, run_dir) = prepare_job(&app_handle, &settings, &input_path, Some(input_key.clone()))?;
 
     // 基于输入文件名生成输出 wav 名称（放在本次处理目录下）
     let file_stem = std::path::Path::new(&input_path)
@@ -751,11 +1538,49 @@ async fn process_media_file_from_path(
     // 获取时长
     let duration: Option<f64> = None; // 不再探测媒体时长
 
+    // 同一输入已转换过时直接复用缓存的音频
+    if settings.result_cache_enabled && !force_rerun.unwrap_or(false)
+        && restore_from_cache(&app_handle, &input_key, |_| output_path.clone(), &log_path)
+    {
+        return Ok(ProcessResult {
+            success: true,
+            message: messages.get("process.cached_audio"),
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            duration_seconds: duration,
+            job_id: Some(job.id),
+        });
+    }
+
+    // 已是 16 kHz 16 位 PCM 的 wav 无需转换，硬链接到任务目录，不占用额外空间
+    if audio::read_wav_info(Path::new(&input_path)).is_ok_and(|info| info.is_whisper_ready(channel_count)) {
+        workspace::link_or_copy(Path::new(&input_path), &output_path)
+            .map_err(|e| MurmurError::io(&output_path, e))
+            .and_then(|_| {
+                audio::validate_for_whisper(&output_path, channel_count)
+                    .map_err(MurmurError::from)
+            })
+            .map_err(|e| {
+                app_handle.state::<workspace::JobRegistry>().release(&job.id);
+                e
+            })?;
+        append_log_line(&log_path, "convert", "输入已是 16 kHz PCM WAV，跳过转换");
+        return Ok(ProcessResult {
+            success: true,
+            message: messages.get("process.no_conversion"),
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            duration_seconds: duration,
+            job_id: Some(job.id),
+        });
+    }
+
     // 执行 ffmpeg 转码
     let ffmpeg_sidecar = app_handle
         .shell()
         .sidecar("ffmpeg")
-        .map_err(|e| format!("无法获取 ffmpeg sidecar: {}", e))?;
+        .map_err(|e| {
+            app_handle.state::<workspace::JobRegistry>().release(&job.id);
+            MurmurError::SidecarUnavailable { name: "ffmpeg".to_string(), detail: e.to_string() }
+        })?;
 
     let args = vec![
         "-i".to_string(),
@@ -763,15 +1588,14 @@ async fn process_media_file_from_path(
         "-ar".to_string(),
         "16000".to_string(),
         "-ac".to_string(),
-        "1".to_string(),
+        channels.clone(),
         output_path.to_string_lossy().to_string(),
         "-y".to_string(),
     ];
 
     let cmd_str = format_cmd_with_args("ffmpeg", &args);
-    println!("执行命令: {}", cmd_str);
     append_log_line(&log_path, "CMD", &cmd_str);
-    let ffmpeg_result = ffmpeg_sidecar.args(&args).output().await;
+    let ffmpeg_result = run_sidecar(ffmpeg_sidecar.args(&args), settings.background_priority, &log_path).await;
 
     match ffmpeg_result {
         Ok(output) => {
@@ -781,108 +1605,486 @@ async fn process_media_file_from_path(
             let err_str = String::from_utf8_lossy(&output.stderr);
             for l in err_str.lines() { append_log_line(&log_path, "ffmpeg:stderr", l); }
 
-            if output.status.success() {
+            if output.success() {
+                // ffmpeg 成功退出也可能产生空文件或全静音的结果
+                if let Err(e) = audio::validate_for_whisper(&output_path, channel_count) {
+                    append_log_line(&log_path, "convert", &format!("转换结果无效: {}", e));
+                    app_handle.state::<workspace::JobRegistry>().release(&job.id);
+                    return Err(e.into());
+                }
+                if settings.result_cache_enabled {
+                    let files = [(output_path.clone(), "audio.wav".to_string())];
+                    store_in_cache(&app_handle, &settings, &input_key, "audio", &input_path, &files, &log_path);
+                }
                 Ok(ProcessResult {
                     success: true,
-                    message: "文件转换成功，准备开始语音识别...".to_string(),
+                    message: messages.get("process.converted"),
                     output_path: Some(output_path.to_string_lossy().to_string()),
                     duration_seconds: duration,
+                    job_id: Some(job.id),
                 })
             } else {
+                // 转换失败的任务不再被引用，留给保留规则回收
+                app_handle.state::<workspace::JobRegistry>().release(&job.id);
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                Err(format!("FFmpeg 执行失败: {}", stderr))
+                Err(MurmurError::FfmpegFailed { detail: stderr.to_string() })
             }
         }
-        Err(e) => Err(format!("无法执行 ffmpeg 命令: {}", e)),
+        Err(e) => {
+            app_handle.state::<workspace::JobRegistry>().release(&job.id);
+            Err(MurmurError::FfmpegSpawn { detail: e.to_string() })
+        }
     }
 }
 
 #[tauri::command]
 async fn save_settings(
     app_handle: tauri::AppHandle,
-    settings: AppSettings
-) -> Result<(), String> {
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("获取应用目录失败: {}", e))?;
+    mut settings: AppSettings
+) -> Result<SettingsCheck, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
     
     if !app_dir.exists() {
         std::fs::create_dir_all(&app_dir)
-            .map_err(|e| format!("创建应用目录失败: {}", e))?;
+            .map_err(|e| MurmurError::io(&app_dir, e))?;
     }
     
-    let settings_file = app_dir.join("settings.json");
-    let settings_json = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("序列化设置失败: {}", e))?;
-    
-    std::fs::write(&settings_file, settings_json)
-        .map_err(|e| format!("保存设置失败: {}", e))?;
-    
-    Ok(())
+    // 托管的字段保留用户原来的取值，托管值不写入用户的设置文件
+    if let Some((_, managed)) = managed_settings(&app_handle) {
+        let stored = settings_to_map(&load_user_settings(&app_dir)?)?;
+        let mut map = settings_to_map(&settings)?;
+        for key in managed.keys() {
+            if let Some(value) = stored.get(key) {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+        settings = serde_json::from_value(serde_json::Value::Object(map))
+            .map_err(|e| MurmurError::SettingsInvalid { detail: e.to_string() })?;
+    }
+
+    let report = normalize_settings(&mut settings, false);
+    if !report.is_ok() {
+        return Err(MurmurError::SettingsInvalid { detail: report.summary() });
+    }
+    if let Some(p) = settings.temp_dir_path.as_ref() {
+        std::fs::create_dir_all(p)
+            .map_err(|e| MurmurError::io(p, e))?;
+    }
+
+    save_user_settings(&app_dir, &settings)?;
+
+    Ok(SettingsCheck { settings: apply_managed(&app_handle, settings), report })
 }
 
+// 只校验不保存，供设置页逐字段显示错误与警告
 #[tauri::command]
-async fn load_settings(app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("获取应用目录失败: {}", e))?;
-    
-    let settings_file = app_dir.join("settings.json");
-    
-    if !settings_file.exists() {
-        return Ok(AppSettings::default());
+async fn validate_settings(mut settings: AppSettings) -> Result<SettingsCheck, MurmurError> {
+    let report = normalize_settings(&mut settings, false);
+    Ok(SettingsCheck { settings, report })
+}
+
+// 生效的设置：用户设置 + 托管配置
+#[tauri::command]
+async fn load_settings(app_handle: tauri::AppHandle) -> Result<AppSettings, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let mut settings = load_user_settings(&app_dir)?;
+    // 未设置模型目录时使用数据目录下的 models（存在时），便携模式可以随程序一起携带模型
+    if settings.whisper_models_path.is_none() && app_dir.join(DEFAULT_MODELS_DIR).is_dir() {
+        settings.whisper_models_path = Some(app_dir.join(DEFAULT_MODELS_DIR).to_string_lossy().to_string());
+    }
+    Ok(apply_managed(&app_handle, settings))
+}
+
+// 用户自己的设置文件，不含托管配置
+fn load_user_settings(app_dir: &Path) -> Result<AppSettings, MurmurError> {
+    let mut loaded = settings_file::load::<AppSettings>(app_dir)
+        .map_err(|detail| MurmurError::SettingsLoad { detail })?;
+    loaded.settings = expand_data_paths(app_dir, &loaded.settings)?;
+    if loaded.used_backup {
+        app_log(app_dir, "settings", "设置文件损坏，已使用备份");
+    }
+    if !loaded.recovered_fields.is_empty() {
+        app_log(
+            app_dir,
+            "settings",
+            &format!("以下设置无法解析，已恢复为默认值: {}", loaded.recovered_fields.join(", ")),
+        );
+    }
+    // 手动编辑或旧版本写入的不合法取值恢复为默认值
+    let before = serde_json::to_value(&loaded.settings).ok();
+    let report = normalize_settings(&mut loaded.settings, true);
+    for issue in &report.warnings {
+        app_log(app_dir, "settings", &format!("{}: {}", issue.field, issue.message));
+    }
+    let normalized = before != serde_json::to_value(&loaded.settings).ok();
+    // 迁移、恢复或规范化后的设置立即写回，写回失败不影响本次读取
+    if loaded.needs_save() || normalized {
+        if let Err(e) = save_user_settings(app_dir, &loaded.settings) {
+            app_log(app_dir, "settings", &format!("写回设置失败: {}", e));
+        }
     }
     
-    let settings_content = std::fs::read_to_string(&settings_file)
-        .map_err(|e| format!("读取设置文件失败: {}", e))?;
-    
-    let settings: AppSettings = serde_json::from_str(&settings_content)
-        .map_err(|e| format!("解析设置失败: {}", e))?;
-    
-    Ok(settings)
+    Ok(loaded.settings)
 }
 
-#[tauri::command]
-async fn start_whisper_recognition(
-    app_handle: tauri::AppHandle,
-    audio_file_path: String,
+const DEFAULT_MODELS_DIR: &str = "models";
+
+// 数据目录内的路径在设置文件中保存为 ${APP_DATA}/...，数据目录整体移动后仍然有效（如便携模式下 U 盘盘符变化）
+fn save_user_settings(app_dir: &Path, settings: &AppSettings) -> Result<(), MurmurError> {
+    let mut map = settings_to_map(settings)?;
+    config_bundle::export_paths(&mut map, &[("APP_DATA", app_dir.to_path_buf())]);
+    settings_file::save(app_dir, &map)
+        .map_err(|detail| MurmurError::SettingsSave { detail })
+}
+
+fn expand_data_paths(app_dir: &Path, settings: &AppSettings) -> Result<AppSettings, MurmurError> {
+    let mut map = settings_to_map(settings)?;
+    config_bundle::import_paths(&mut map, &[("APP_DATA", app_dir.to_path_buf())]);
+    serde_json::from_value(serde_json::Value::Object(map))
+        .map_err(|e| MurmurError::SettingsLoad { detail: e.to_string() })
+}
+
+fn settings_to_map(settings: &AppSettings) -> Result<serde_json::Map<String, serde_json::Value>, MurmurError> {
+    match serde_json::to_value(settings) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(MurmurError::Internal { detail: "settings did not serialize to a JSON object".to_string() }),
+        Err(e) => Err(MurmurError::Internal { detail: e.to_string() }),
+    }
+}
+
+// 把部分设置覆盖到 base 上并校验，excluded 中的字段不覆盖。lenient 用于激活旧的配置方案、导入与托管配置：
+// 无法解析或不合法的字段恢复为默认值；否则（单次任务的覆盖、新建方案）任何问题都作为错误返回
+fn overlay_settings(
+    base: &AppSettings,
+    overrides: &serde_json::Map<String, serde_json::Value>,
+    excluded: &[&str],
+    lenient: bool,
+) -> Result<SettingsCheck, MurmurError> {
+    let mut map = settings_to_map(base)?;
+    profiles::overlay(&mut map, overrides, excluded);
+    let mut settings = if lenient {
+        settings_file::recover_fields::<AppSettings>(&map)
+            .map_err(|detail| MurmurError::SettingsInvalid { detail })?
+            .0
+    } else {
+        serde_json::from_value(serde_json::Value::Object(map))
+            .map_err(|e| MurmurError::SettingsInvalid { detail: e.to_string() })?
+    };
+    let report = normalize_settings(&mut settings, lenient);
+    if !report.is_ok() {
+        return Err(MurmurError::SettingsInvalid { detail: report.summary() });
+    }
+    Ok(SettingsCheck { settings, report })
+}
+
+#[tauri::command]
+async fn list_profiles(app_handle: tauri::AppHandle) -> Result<profiles::ProfileStore, MurmurError> {
+    profiles::ProfileStore::load(&app_data_dir(&app_handle)?)
+}
+
+// 新建配置方案；未提供设置时以当前保存的设置为准
+#[tauri::command]
+async fn create_profile(
+    app_handle: tauri::AppHandle,
+    name: String,
+    settings: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<profiles::Profile, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let current = load_settings(app_handle.clone()).await?;
+    let mut map = match settings {
+        Some(partial) => {
+            overlay_settings(&current, &partial, PROFILE_EXCLUDED_SETTINGS, false)?;
+            partial
+        }
+        None => settings_to_map(&current)?,
+    };
+    profiles::strip(&mut map, PROFILE_EXCLUDED_SETTINGS);
+
+    let mut store = profiles::ProfileStore::load(&app_dir)?;
+    let profile = store.create(&name, map, workspace::now_secs())?.clone();
+    store.save(&app_dir)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+async fn rename_profile(
+    app_handle: tauri::AppHandle,
+    name: String,
+    new_name: String,
+) -> Result<profiles::ProfileStore, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let mut store = profiles::ProfileStore::load(&app_dir)?;
+    store.rename(&name, &new_name, workspace::now_secs())?;
+    store.save(&app_dir)?;
+    Ok(store)
+}
+
+#[tauri::command]
+async fn duplicate_profile(
+    app_handle: tauri::AppHandle,
+    name: String,
+    new_name: String,
+) -> Result<profiles::Profile, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let mut store = profiles::ProfileStore::load(&app_dir)?;
+    let profile = store.duplicate(&name, &new_name, workspace::now_secs())?.clone();
+    store.save(&app_dir)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+async fn delete_profile(app_handle: tauri::AppHandle, name: String) -> Result<profiles::ProfileStore, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let mut store = profiles::ProfileStore::load(&app_dir)?;
+    store.delete(&name)?;
+    store.save(&app_dir)?;
+    Ok(store)
+}
+
+// 激活配置方案：覆盖到当前设置上并保存为默认设置
+#[tauri::command]
+async fn activate_profile(app_handle: tauri::AppHandle, name: String) -> Result<SettingsCheck, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let mut store = profiles::ProfileStore::load(&app_dir)?;
+    let current = load_user_settings(&app_dir)?;
+    let mut check = overlay_settings(&current, &store.get(&name)?.settings, PROFILE_EXCLUDED_SETTINGS, true)?;
+
+    save_user_settings(&app_dir, &check.settings)?;
+    store.active = Some(name);
+    store.save(&app_dir)?;
+    check.settings = apply_managed(&app_handle, check.settings);
+    Ok(check)
+}
+
+// 可移植路径的前缀，较具体的目录在前
+fn path_bases(app_handle: &tauri::AppHandle) -> Vec<(&'static str, PathBuf)> {
+    let mut bases = Vec::new();
+    if let Ok(dir) = app_data_dir(app_handle) {
+        bases.push(("APP_DATA", dir));
+    }
+    if let Ok(dir) = app_handle.path().home_dir() {
+        bases.push(("HOME", dir));
+    }
+    bases
+}
+
+// 托管配置相关的事件写入应用日志
+fn managed_log(app_handle: &tauri::AppHandle, line: &str) {
+    if let Ok(app_dir) = app_data_dir(app_handle) {
+        app_log(&app_dir, "managed", line);
+    }
+}
+
+// 托管配置文件及其中的设置；文件无法读取时忽略并记录日志，不影响使用
+fn managed_settings(app_handle: &tauri::AppHandle) -> Option<(PathBuf, serde_json::Map<String, serde_json::Value>)> {
+    let path = config_bundle::managed_config_path()?;
+    match config_bundle::load_managed(&path, &path_bases(app_handle)) {
+        Ok(settings) => Some((path, settings)),
+        Err(e) => {
+            managed_log(app_handle, &format!("忽略托管配置 {}: {}", path.display(), e));
+            None
+        }
+    }
+}
+
+fn apply_managed(app_handle: &tauri::AppHandle, settings: AppSettings) -> AppSettings {
+    let Some((path, managed)) = managed_settings(app_handle) else {
+        return settings;
+    };
+    match overlay_settings(&settings, &managed, &["schema_version"], true) {
+        Ok(check) => {
+            for issue in &check.report.warnings {
+                managed_log(app_handle, &format!("托管配置 {}: {}", issue.field, issue.message));
+            }
+            check.settings
+        }
+        Err(e) => {
+            managed_log(app_handle, &format!("忽略托管配置 {}: {}", path.display(), e));
+            settings
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ManagedConfig {
+    path: Option<String>,
+    // 被托管的顶层字段，设置页据此锁定对应的控件
+    fields: Vec<String>,
+}
+
+#[tauri::command]
+async fn get_managed_config(app_handle: tauri::AppHandle) -> Result<ManagedConfig, MurmurError> {
+    Ok(match managed_settings(&app_handle) {
+        Some((path, managed)) => ManagedConfig {
+            path: Some(path.display().to_string()),
+            fields: managed.keys().cloned().collect(),
+        },
+        None => ManagedConfig { path: None, fields: Vec::new() },
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct ExportReport {
+    path: String,
+    profile_count: usize,
+    // 仍是本机绝对路径、导入方需要重新设置的字段
+    machine_paths: Vec<String>,
+}
+
+// 导出用户设置与全部配置方案到一个文件
+#[tauri::command]
+async fn export_config(app_handle: tauri::AppHandle, path: String) -> Result<ExportReport, MurmurError> {
+    let path = validation::normalize_path(&path).ok_or_else(|| MurmurError::InvalidPath { path: path.clone() })?;
+    let app_dir = app_data_dir(&app_handle)?;
+    let bases = path_bases(&app_handle);
+
+    let mut settings = settings_to_map(&load_user_settings(&app_dir)?)?;
+    let mut machine_paths = config_bundle::export_paths(&mut settings, &bases);
+    let mut profiles = profiles::ProfileStore::load(&app_dir)?.profiles;
+    for profile in &mut profiles {
+        for field in config_bundle::export_paths(&mut profile.settings, &bases) {
+            machine_paths.push(format!("{}: {}", profile.name, field));
+        }
+    }
+
+    let bundle = config_bundle::Bundle {
+        format: config_bundle::BUNDLE_FORMAT.to_string(),
+        version: config_bundle::BUNDLE_VERSION,
+        exported_at: chrono::Local::now().to_rfc3339(),
+        settings,
+        profiles,
+        machine_paths: machine_paths.clone(),
+    };
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| MurmurError::ConfigSerialize { detail: e.to_string() })?;
+    settings_file::write_atomic(Path::new(&path), json.as_bytes(), || {})
+        .map_err(|e| MurmurError::io(&path, e))?;
+
+    Ok(ExportReport { path, profile_count: bundle.profiles.len(), machine_paths })
+}
+
+#[derive(Debug, Serialize)]
+struct ImportReport {
+    // 导入后生效的设置（含托管配置）
+    settings: AppSettings,
+    #[serde(flatten)]
+    report: validation::Report,
+    conflicts: Vec<config_bundle::Conflict>,
+    // 被导入内容覆盖的本地配置方案
+    profile_conflicts: Vec<String>,
+    // 需要在本机重新设置的路径字段
+    machine_paths: Vec<String>,
+    applied: bool,
+}
+
+// 导入配置文件；dry_run 只返回冲突与校验结果，不写入
+#[tauri::command]
+async fn import_config(
+    app_handle: tauri::AppHandle,
+    path: String,
+    mode: config_bundle::ImportMode,
+    dry_run: Option<bool>,
+) -> Result<ImportReport, MurmurError> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let bases = path_bases(&app_handle);
+    let content = std::fs::read_to_string(&path).map_err(|e| MurmurError::io(&path, e))?;
+    let mut bundle = config_bundle::parse(&content)
+        .map_err(|detail| MurmurError::ConfigInvalid { path: path.clone(), detail })?;
+
+    // 旧版本导出的设置按设置文件的迁移步骤升级
+    settings_file::migrate(&mut bundle.settings);
+    let mut machine_paths = config_bundle::import_paths(&mut bundle.settings, &bases);
+    for profile in &mut bundle.profiles {
+        for field in config_bundle::import_paths(&mut profile.settings, &bases) {
+            machine_paths.push(format!("{}: {}", profile.name, field));
+        }
+    }
+
+    let current = load_user_settings(&app_dir)?;
+    let conflicts = config_bundle::diff(&settings_to_map(&current)?, &bundle.settings, &["schema_version"]);
+    let base = match mode {
+        config_bundle::ImportMode::Merge => current,
+        config_bundle::ImportMode::Replace => AppSettings::default(),
+    };
+    let check = overlay_settings(&base, &bundle.settings, &["schema_version"], true)?;
+
+    let mut store = profiles::ProfileStore::load(&app_dir)?;
+    let profile_conflicts = config_bundle::merge_profiles(&mut store, bundle.profiles, mode);
+
+    let applied = !dry_run.unwrap_or(false);
+    if applied {
+        save_user_settings(&app_dir, &check.settings)?;
+        store.save(&app_dir)?;
+    }
+
+    Ok(ImportReport {
+        settings: apply_managed(&app_handle, check.settings),
+        report: check.report,
+        conflicts,
+        profile_conflicts,
+        machine_paths,
+        applied,
+    })
+}
+
+// VAD 预设，供设置页一键填充
+#[tauri::command]
+async fn get_vad_presets() -> Result<Vec<(String, vad::VadSettings)>, MurmurError> {
+    Ok(vad::PRESET_NAMES
+        .iter()
+        .filter_map(|name| vad::VadSettings::preset(name).map(|p| (name.to_string(), p)))
+        .collect())
+}
+
+#[tauri::command]
+async fn start_whisper_recognition(
+    app_handle: tauri::AppHandle,
+    audio_file_path: String,
     total_duration: Option<f64>, // 添加总时长参数
+    force_rerun: Option<bool>,
+    // 仅对本次任务生效的部分设置，不写入保存的设置
+    overrides: Option<serde_json::Map<String, serde_json::Value>>,
     state: tauri::State<'_, WhisperProcState>,
-) -> Result<(), String> {
+) -> Result<(), MurmurError> {
     use tauri_plugin_shell::ShellExt;
     use tauri::path::BaseDirectory;
     
-    // 加载设置
-    let settings = load_settings(app_handle.clone()).await
-        .map_err(|e| format!("加载设置失败: {}", e))?;
+    // 加载设置，并应用本次任务的覆盖
+    let mut settings = load_settings(app_handle.clone()).await?;
+    if let Some(overrides) = overrides.filter(|o| !o.is_empty()) {
+        settings = overlay_settings(&settings, &overrides, PROFILE_EXCLUDED_SETTINGS, false)?.settings;
+    }
+    let messages = i18n::Messages::new(&settings.app_locale);
     
     // 允许选择内置打包模型：无需配置 models 路径
     let embedded_model_name = "ggml-tiny-q5_1.bin".to_string();
     let using_embedded = settings.whisper_model == embedded_model_name;
-    let (model_file, models_dir_display) = if using_embedded {
+    let model_file = if using_embedded {
         // 解析打包资源中的模型路径
         let p = app_handle
             .path()
             .resolve("resources/ggml-tiny-q5_1.bin", BaseDirectory::Resource)
-            .map_err(|e| format!("解析内置模型路径失败: {}", e))?;
-        (p, "<内置模型>".to_string())
+            .map_err(|e| MurmurError::BuiltinModelUnavailable { detail: e.to_string() })?;
+        if !p.exists() {
+            return Err(MurmurError::BuiltinModelUnavailable { detail: p.display().to_string() });
+        }
+        p
     } else {
         // 借用而不是移动 whisper_models_path，避免后续使用
         let whisper_models_path = settings
             .whisper_models_path
             .as_ref()
-            .ok_or("请在设置中配置 Whisper Models 路径")?;
+            .ok_or(MurmurError::ModelsPathNotConfigured)?;
         let p = std::path::Path::new(whisper_models_path).join(&settings.whisper_model);
-        (p, whisper_models_path.to_string())
+        // 检查模型文件是否存在
+        if !p.exists() {
+            return Err(MurmurError::ModelNotFound {
+                model: settings.whisper_model.clone(),
+                dir: whisper_models_path.to_string(),
+            });
+        }
+        p
     };
     
-    // 检查模型文件是否存在
-    if !model_file.exists() {
-        return Err(format!(
-            "模型文件不存在: {}\n\n请确保在 Models 目录 ({}) 中有对应的模型文件。\n\n您可以从以下地址下载模型：\n- https://huggingface.co/ggerganov/whisper.cpp/tree/main\n- 或使用 whisper.cpp 的 download-ggml-model.sh 脚本",
-            settings.whisper_model,
-            models_dir_display
-        ));
-    }
-    
     // 检查 Core ML 优化支持
     let has_coreml_support = check_coreml_support(app_handle.clone(), settings.whisper_model.clone()).await
         .unwrap_or(false);
@@ -904,7 +2106,7 @@ async fn start_whisper_recognition(
         _ => "whisper-cli",
     };
     let whisper_sidecar = app_handle.shell().sidecar(selected_cli_name)
-        .map_err(|e| format!("无法获取 {} sidecar: {}", selected_cli_name, e))?;
+        .map_err(|e| MurmurError::SidecarUnavailable { name: selected_cli_name.to_string(), detail: e.to_string() })?;
     
     // 构建命令参数，总是传递 -l 参数
     let mut args = vec![
@@ -919,16 +2121,50 @@ async fn start_whisper_recognition(
         "--print-progress".to_string() // 推理进度
     ];
 
+    // 输出 JSON，供后处理生成统一的转写结果；完整 JSON 额外包含 token 级时间戳与概率。
+    // 不使用 -ml 1，以免把段落拆成单词，词级结果由 token 拼接得到
+    if settings.word_timestamps {
+        args.push("--output-json-full".to_string());
+        // DTW 对齐需要指定模型对应的预设，无法识别的模型名直接跳过
+        if settings.dtw_timestamps {
+            if let Some(preset) = dtw_preset(&settings.whisper_model) {
+                args.push("--dtw".to_string());
+                args.push(preset);
+            }
+        }
+    } else {
+        args.push("--output-json".to_string());
+    }
+
+    // 立体声通话录音：whisper-cli 按左右声道能量判断每段的说话人
+    if settings.stereo_diarize {
+        args.push("--diarize".to_string());
+    }
+
+    // tinydiarize 模型（文件名含 tdrz）可以标记说话人切换
+    if is_tinydiarize_model(&settings.whisper_model) {
+        args.push("--tinydiarize".to_string());
+    }
+
     // 如果启用 VAD，附加 vad 参数
     if settings.enable_vad {
-        // 解析打包到资源目录下的 VAD 模型
-        let vad_path = app_handle
-            .path()
-            .resolve("resources/ggml-silero-v5.1.2.bin", BaseDirectory::Resource)
-            .map_err(|e| format!("解析 VAD 资源路径失败: {}", e))?;
-        args.push("--vad".to_string());
-        args.push("--vad-model".to_string());
-        args.push(vad_path.to_string_lossy().to_string());
+        settings.vad.validate()
+            .map_err(|e| MurmurError::SettingsInvalid { detail: format!("{}: {}", e.field(), e.message(&messages)) })?;
+        // 优先使用用户提供的 VAD 模型，否则解析打包到资源目录下的模型
+        let vad_path = match &settings.vad.model_path {
+            Some(p) if !p.trim().is_empty() => {
+                let p = PathBuf::from(p);
+                if !p.exists() {
+                    return Err(MurmurError::FileNotFound { path: p.display().to_string() });
+                }
+                p
+            }
+            _ => app_handle
+                .path()
+                .resolve("resources/ggml-silero-v5.1.2.bin", BaseDirectory::Resource)
+                .map_err(|e| MurmurError::BuiltinModelUnavailable { detail: e.to_string() })?,
+        };
+        args.extend(settings.vad.to_args(&vad_path.to_string_lossy()));
     }
 
     // 如果设置了禁用 GPU，追加 --no-gpu
@@ -936,41 +2172,150 @@ async fn start_whisper_recognition(
         args.push("--no-gpu".to_string());
     }
 
-    // 线程数量（1-8），默认 4
-    let tc = settings.thread_count.clamp(1, 8);
+    // 线程数：自动时按物理核心与当前负载选择；多个处理器时每个处理器使用这么多线程
+    let cpu_info = cpu::CpuInfo::detect();
+    let thread_plan = cpu::plan(settings.thread_count, settings.processor_count, &cpu_info);
     args.push("--threads".to_string());
-    args.push(tc.to_string());
+    args.push(thread_plan.threads.to_string());
+    if thread_plan.processors > 1 {
+        args.push("--processors".to_string());
+        args.push(thread_plan.processors.to_string());
+    }
     
     // 准备日志路径（和 wav 同目录，<stem>_log.txt）
     let audio_p = PathBuf::from(&audio_file_path);
     let stem = audio_p.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
     let log_path = audio_p.parent().unwrap_or_else(|| Path::new(".")).join(format!("{}_log.txt", stem));
     let cmd_str = format_cmd_with_args(selected_cli_name, &args);
-    println!("执行命令: {}", cmd_str);
     append_log_line(&log_path, "CMD", &cmd_str);
+    append_log_line(&log_path, "threads", &thread_plan.describe(&cpu_info));
+
+    // 说话人聚类模型：使用用户指定的文件（设置校验保证启用时已指定且功能已编译）
+    let speaker_model = match settings.speaker_model_path.as_deref().filter(|_| settings.speaker_clustering) {
+        Some(p) => {
+            let p = PathBuf::from(p);
+            if !p.is_file() {
+                return Err(MurmurError::FileNotFound { path: p.display().to_string() });
+            }
+            Some(p)
+        }
+        None => None,
+    };
+
+    // 启用脱敏时，实时输出与日志中的文本同样需要脱敏；规则无效时不启动识别
+    let redactor = if settings.redaction_enabled {
+        Some(redaction::Redactor::new(&settings.redaction_custom_patterns)?)
+    } else {
+        None
+    };
+
+    // 结果缓存：输入与生效设置都相同时直接复用上次的结果；force_rerun 时重新识别并刷新缓存
+    let cache_key = if settings.result_cache_enabled {
+        let input_key = match workspace::job_meta_of(&audio_p).and_then(|m| m.input_key) {
+            Some(k) => k,
+            None => cache::hash_file(&audio_p).map_err(|e| MurmurError::io(&audio_p, e))?,
+        };
+        Some(result_key(&input_key, &settings, &model_file))
+    } else {
+        None
+    };
+    if let Some(key) = cache_key.as_ref().filter(|_| !force_rerun.unwrap_or(false)) {
+        let target = |name: &str| transcript::output_path(&audio_file_path, name);
+        if restore_from_cache(&app_handle, key, target, &log_path) {
+            let _ = app_handle.emit("whisper-output", messages.get("whisper.cached_result"));
+            let _ = app_handle.emit("whisper-complete", messages.get("whisper.complete"));
+            return Ok(());
+        }
+    }
 
     // 启动进程并实时读取输出
     let (mut rx, child) = whisper_sidecar
         .args(&args)
         .spawn()
-        .map_err(|e| format!("启动 {} 失败: {}", selected_cli_name, e))?;
+        .map_err(|e| MurmurError::WhisperSpawn { name: selected_cli_name.to_string(), detail: e.to_string() })?;
+    let pid = child.pid();
+    if settings.background_priority {
+        match priority::lower_priority(pid) {
+            Ok(()) => append_log_line(&log_path, "priority", "已降低识别进程的优先级"),
+            Err(e) => append_log_line(&log_path, "priority", &format!("降低识别进程的优先级失败: {}", e)),
+        }
+    }
     // 保存子进程句柄
     {
         let mut guard = state.child.lock().await;
         *guard = Some(child);
     }
+    // 识别期间持有任务引用，避免被其他任务触发的清理回收
+    let job_id = workspace::job_id_of(&audio_p);
+    if let Some(id) = &job_id {
+        app_handle.state::<workspace::JobRegistry>().acquire(id);
+    }
     
+    let control = priority::JobControl::new(pid, priority::ThrottleStatus::new(settings.background_priority, settings.cpu_cap_percent));
+    let initial_status = control.snapshot();
+    if let Ok(mut guard) = state.control.lock() {
+        *guard = Some(control);
+    }
+    publish_whisper_status(&app_handle, Some(initial_status));
+    // 识别进程结束后停止负载监控
+    let finished = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    if settings.cpu_cap_percent > 0 {
+        let app_handle = app_handle.clone();
+        let log_path = log_path.clone();
+        let finished = finished.clone();
+        let cap = settings.cpu_cap_percent;
+        tokio::spawn(async move {
+            let mut sampler = priority::LoadSampler::new(pid);
+            let mut throttle = priority::Throttle::new(cap);
+            loop {
+                tokio::time::sleep(priority::SAMPLE_INTERVAL).await;
+                if finished.load(std::sync::atomic::Ordering::SeqCst) {
+                    break;
+                }
+                let load = sampler.sample();
+                let Some(pause) = throttle.update(load) else {
+                    continue;
+                };
+                match with_job_control(&app_handle, |control| control.set_throttled(pause, load).map(|()| control.snapshot())) {
+                    Some(Ok(status)) => {
+                        let action = if pause { "系统繁忙，暂停识别" } else { "负载回落，恢复识别" };
+                        append_log_line(&log_path, "throttle", &format!("{}（其他程序占用 {:.0}%）", action, load));
+                        publish_whisper_status(&app_handle, Some(status));
+                    }
+                    Some(Err(e)) => append_log_line(&log_path, "throttle", &format!("暂停或恢复识别进程失败: {}", e)),
+                    None => break,
+                }
+            }
+        });
+    }
+
     let app_handle_clone = app_handle.clone();
     let log_path_clone = log_path.clone();
-    
+    let audio_path_clone = audio_file_path.clone();
+    let script_target = opencc::ScriptTarget::from_setting(&settings.output_script);
+    // 实时输出同样只转换中文；自动检测语言时等 whisper 报告检测结果后再决定
+    let mut stream_target = script_target.filter(|_| opencc::applies_to(&settings.whisper_language));
+
     // 在新的任务中处理输出
     tokio::spawn(async move {
         use tauri_plugin_shell::process::CommandEvent;
-        
+
+        // 停止负载监控并撤下暂停控制，可重复调用
+        let detach_control = || {
+            finished.store(true, std::sync::atomic::Ordering::SeqCst);
+            if let Ok(mut guard) = app_handle_clone.state::<WhisperProcState>().control.lock() {
+                *guard = None;
+            }
+        };
+
         while let Some(event) = rx.recv().await {
             match event {
                 CommandEvent::Stdout(data) => {
                     if let Ok(line) = String::from_utf8(data) {
+                        let line = match &redactor {
+                            Some(r) => r.redact(&line),
+                            None => line,
+                        };
                         let trimmed_line = line.trim();
                         if !trimmed_line.is_empty() {
                             append_log_line(&log_path_clone, "whisper:stdout", trimmed_line);
@@ -983,6 +2328,7 @@ async fn start_whisper_recognition(
                                         current_seconds: current_time,
                                         total_seconds: total,
                                         percentage,
+                                        eta_seconds: with_job_control(&app_handle_clone, |c| c.eta(percentage)).flatten(),
                                     };
                                     let _ = app_handle_clone.emit("whisper-progress", progress_info);
                                 }
@@ -992,7 +2338,12 @@ async fn start_whisper_recognition(
                             if !trimmed_line.starts_with("whisper_") && 
                                !trimmed_line.contains("processing") &&
                                !trimmed_line.contains("load time") {
-                                let _ = app_handle_clone.emit("whisper-output", trimmed_line);
+                                // 实时输出与最终结果使用相同的字形
+                                let display_line = match stream_target {
+                                    Some(target) => opencc::convert(trimmed_line, target),
+                                    None => trimmed_line.to_string(),
+                                };
+                                let _ = app_handle_clone.emit("whisper-output", display_line);
                             }
                         }
                     }
@@ -1002,10 +2353,15 @@ async fn start_whisper_recognition(
                         let trimmed_line = line.trim();
                         if !trimmed_line.is_empty() {
                             append_log_line(&log_path_clone, "whisper:stderr", trimmed_line);
+                            if let Some(language) = extract_detected_language(trimmed_line) {
+                                stream_target = script_target.filter(|_| opencc::applies_to(language));
+                            }
                             // 尝试解析 --print-progress 的进度行
                             if let Some(pct) = extract_percentage_from_progress_line(trimmed_line) {
                                 let (cur, total) = if let Some(total) = total_duration { (pct * total / 100.0, total) } else { (0.0, 0.0) };
-                                let progress_info = ProgressInfo { current_seconds: cur, total_seconds: total, percentage: pct.min(100.0) };
+                                let percentage = pct.min(100.0);
+                                let eta_seconds = with_job_control(&app_handle_clone, |c| c.eta(percentage)).flatten();
+                                let progress_info = ProgressInfo { current_seconds: cur, total_seconds: total, percentage, eta_seconds };
                                 let _ = app_handle_clone.emit("whisper-progress", progress_info);
                             } else {
                                 // 其他 stderr 输出作为错误事件
@@ -1015,19 +2371,65 @@ async fn start_whisper_recognition(
                     }
                 }
                 CommandEvent::Terminated(payload) => {
+                    // 进程已退出，PID 可能被系统复用：先撤下暂停控制，负载监控与手动暂停都不再向该 PID 发送信号，
+                    // 再进行耗时的后处理
+                    detach_control();
                     append_log_line(&log_path_clone, "whisper", &format!("terminated: {:?}", payload.code));
                     if let Some(code) = payload.code {
                         if code == 0 {
-                            let _ = app_handle_clone.emit("whisper-complete", "Whisper 识别完成");
+                            // 后处理（声纹推理与聚类等）是耗时的同步计算，放到阻塞线程中执行
+                            let finalized = {
+                                let audio_path = audio_path_clone.clone();
+                                let settings = settings.clone();
+                                let speaker_model = speaker_model.clone();
+                                let log_path = log_path_clone.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    finalize_transcript(&audio_path, &settings, speaker_model.as_deref(), &log_path)
+                                })
+                                .await
+                                .unwrap_or_else(|e| Err(e.to_string()))
+                            };
+                            let completed = match finalized {
+                                Ok(_) => {
+                                    if let Some(key) = &cache_key {
+                                        let files: Vec<(PathBuf, String)> = RESULT_FILES
+                                            .iter()
+                                            .map(|name| (transcript::output_path(&audio_path_clone, name), name.to_string()))
+                                            .filter(|(path, _)| path.exists())
+                                            .collect();
+                                        let source = workspace::job_meta_of(Path::new(&audio_path_clone))
+                                            .map_or_else(|| audio_path_clone.clone(), |m| m.source);
+                                        store_in_cache(&app_handle_clone, &settings, key, "result", &source, &files, &log_path_clone);
+                                    }
+                                    true
+                                }
+                                Err(e) if settings.redaction_enabled => {
+                                    // 启用脱敏时 whisper 的原始输出含有未脱敏文本，不能作为结果保留，任务按失败处理
+                                    append_log_line(&log_path_clone, "postprocess", &format!("后处理失败: {}", e));
+                                    let removed = remove_result_files(&audio_path_clone);
+                                    append_log_line(&log_path_clone, "redaction", &format!("已删除未脱敏的输出: {} 个文件", removed));
+                                    let _ = app_handle_clone.emit("whisper-error", messages.get("whisper.redaction_failed"));
+                                    false
+                                }
+                                Err(e) => {
+                                    append_log_line(&log_path_clone, "postprocess", &format!("后处理失败: {}", e));
+                                    let _ = app_handle_clone.emit("whisper-error", messages.get("whisper.postprocess_failed"));
+                                    true
+                                }
+                            };
+                            if completed {
+                                let _ = app_handle_clone.emit("whisper-complete", messages.get("whisper.complete"));
+                            }
                         } else {
-                            let _ = app_handle_clone.emit("whisper-error", format!("Whisper 进程异常退出: {}", code));
+                            let _ = app_handle_clone.emit("whisper-error", messages.format("whisper.exit_code", &[("code", &code)]));
                         }
                     }
                     break;
                 }
                 CommandEvent::Error(error) => {
+                    detach_control();
                     append_log_line(&log_path_clone, "whisper", &format!("error: {}", error));
-                    let _ = app_handle_clone.emit("whisper-error", format!("Whisper 进程错误: {}", error));
+                    let _ = app_handle_clone.emit("whisper-error", messages.format("whisper.process_error", &[("error", &error)]));
                     break;
                 }
                 _ => {
@@ -1035,6 +2437,11 @@ async fn start_whisper_recognition(
                 }
             }
         }
+        detach_control();
+        publish_whisper_status(&app_handle_clone, None);
+        if let Some(id) = &job_id {
+            app_handle_clone.state::<workspace::JobRegistry>().release(id);
+        }
     });
     
     Ok(())
@@ -1044,64 +2451,34 @@ async fn start_whisper_recognition(
 async fn check_model_exists(
     app_handle: tauri::AppHandle,
     model_name: String,
-) -> Result<bool, String> {
+) -> Result<bool, MurmurError> {
     // 内置模型：直接返回存在
     if model_name == "ggml-tiny-q5_1.bin" {
         return Ok(true);
     }
     // 加载设置
-    let settings = load_settings(app_handle.clone()).await
-        .map_err(|e| format!("加载设置失败: {}", e))?;
+    let settings = load_settings(app_handle.clone()).await?;
     
+    // 路径已在读取设置时规范化
     let whisper_models_path = settings.whisper_models_path
-        .ok_or("请在设置中配置 Whisper Models 路径")?;
-    
-    // 兼容不同平台/来源的路径字符串（可能来自旧实现或 file:// 前缀）
-    let mut base = whisper_models_path.trim().to_string();
-    if base.starts_with("file://") {
-        base = base[7..].to_string(); // 去掉前缀
-        // Windows 的 file URL 可能以 "/C:/" 开头，去掉首个 '/'
-        if base.starts_with('/') {
-            let bytes = base.as_bytes();
-            if bytes.len() > 2 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
-                base = base[1..].to_string();
-            }
-        }
-    }
-    // 去除首尾引号
-    base = base.trim_matches('"').to_string();
+        .ok_or(MurmurError::ModelsPathNotConfigured)?;
 
-    let base_path = std::path::PathBuf::from(base);
-    let model_file = base_path.join(&model_name);
+    let model_file = std::path::PathBuf::from(whisper_models_path).join(&model_name);
     Ok(model_file.exists())
 }
 
 #[tauri::command]
-async fn list_downloaded_models(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+async fn list_downloaded_models(app_handle: tauri::AppHandle) -> Result<Vec<String>, MurmurError> {
     // 加载设置，若未配置路径则返回空列表
-    let settings = load_settings(app_handle.clone()).await
-        .map_err(|e| format!("加载设置失败: {}", e))?;
+    let settings = load_settings(app_handle.clone()).await?;
 
     let whisper_models_path = match settings.whisper_models_path {
         Some(p) => p,
         None => return Ok(Vec::new()),
     };
 
-    // 兼容可能的 file:// 前缀与首尾引号
-    let mut base = whisper_models_path.trim().to_string();
-    if base.starts_with("file://") {
-        base = base[7..].to_string();
-        if base.starts_with('/') {
-            let bytes = base.as_bytes();
-            if bytes.len() > 2 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
-                base = base[1..].to_string();
-            }
-        }
-    }
-    base = base.trim_matches('"').to_string();
-
     let mut result: Vec<String> = Vec::new();
-    let dir_path = std::path::PathBuf::from(&base);
+    let dir_path = std::path::PathBuf::from(&whisper_models_path);
     if !dir_path.exists() || !dir_path.is_dir() {
         return Ok(result);
     }
@@ -1133,23 +2510,22 @@ async fn list_downloaded_models(app_handle: tauri::AppHandle) -> Result<Vec<Stri
 async fn check_coreml_support(
     app_handle: tauri::AppHandle,
     model_name: String,
-) -> Result<bool, String> {
+) -> Result<bool, MurmurError> {
     // 内置 ggml 模型不涉及 Core ML 打包
     if model_name == "ggml-tiny-q5_1.bin" {
         return Ok(false);
     }
     // 加载设置
-    let settings = load_settings(app_handle.clone()).await
-        .map_err(|e| format!("加载设置失败: {}", e))?;
+    let settings = load_settings(app_handle.clone()).await?;
     
     let whisper_models_path = settings.whisper_models_path
-        .ok_or("请在设置中配置 Whisper Models 路径")?;
+        .ok_or(MurmurError::ModelsPathNotConfigured)?;
     
     // 获取模型文件的基本名称（不含扩展名）
     let model_stem = std::path::Path::new(&model_name)
         .file_stem()
         .and_then(|s| s.to_str())
-        .ok_or("无效的模型文件名")?;
+        .ok_or_else(|| MurmurError::InvalidPath { path: model_name.clone() })?;
     
     let models_dir = std::path::Path::new(&whisper_models_path);
     
@@ -1171,37 +2547,100 @@ async fn check_coreml_support(
 }
 
 #[tauri::command]
-async fn stop_whisper_recognition(app_handle: tauri::AppHandle, state: tauri::State<'_, WhisperProcState>) -> Result<(), String> {
+async fn stop_whisper_recognition(app_handle: tauri::AppHandle, state: tauri::State<'_, WhisperProcState>) -> Result<(), MurmurError> {
     // 终止正在运行的 whisper 进程
     if let Some(child) = state.child.lock().await.take() {
         // 尝试优雅终止，若不支持则直接 kill
         if let Err(e) = child.kill() {
-            return Err(format!("停止 Whisper 失败: {}", e));
+            return Err(MurmurError::WhisperStop { detail: e.to_string() });
         }
         let _ = app_handle.emit("whisper-stopped", "stopped");
     }
     Ok(())
 }
 
+// 暂停当前识别：挂起识别进程，已完成的进度保留
+#[tauri::command]
+async fn pause_job(app_handle: tauri::AppHandle) -> Result<priority::ThrottleStatus, MurmurError> {
+    set_job_paused(&app_handle, true)
+}
+
+// 恢复被手动暂停的识别
+#[tauri::command]
+async fn resume_job(app_handle: tauri::AppHandle) -> Result<priority::ThrottleStatus, MurmurError> {
+    set_job_paused(&app_handle, false)
+}
+
+// 当前识别进程的优先级与暂停状态，供页面重新打开时恢复显示
+#[tauri::command]
+async fn get_whisper_status(app_handle: tauri::AppHandle) -> Result<Option<priority::ThrottleStatus>, MurmurError> {
+    Ok(with_job_control(&app_handle, |control| control.snapshot()))
+}
+
+// 按保留规则清理任务工作区，dry_run 时只返回将被清理的任务
+#[tauri::command]
+async fn cleanup_jobs(app_handle: tauri::AppHandle, dry_run: Option<bool>) -> Result<workspace::CleanupReport, MurmurError> {
+    let settings = load_settings(app_handle.clone()).await?;
+    let app_dir = app_data_dir(&app_handle)?;
+    let registry = app_handle.state::<workspace::JobRegistry>();
+    Ok(workspace::cleanup(
+        &resolve_temp_dir(&app_dir, &settings),
+        Some(&app_dir.join("temp")),
+        retention_policy(&settings),
+        &registry,
+        &process_alive,
+        dry_run.unwrap_or(false),
+    ))
+}
+
+// 结果缓存列表（最近使用的在前）
+#[tauri::command]
+async fn list_cache_entries(app_handle: tauri::AppHandle) -> Result<Vec<cache::CacheEntry>, MurmurError> {
+    Ok(result_cache(&app_handle)?.list())
+}
+
+// 删除指定的缓存记录，keys 为空时清空全部缓存
+#[tauri::command]
+async fn evict_cache(app_handle: tauri::AppHandle, keys: Option<Vec<String>>) -> Result<cache::EvictReport, MurmurError> {
+    Ok(result_cache(&app_handle)?.evict(keys.as_deref()))
+}
+
+// 前端不再需要某个任务（例如关闭结果或开始新文件）时释放引用
+#[tauri::command]
+async fn release_job(app_handle: tauri::AppHandle, job_id: String) -> Result<(), MurmurError> {
+    app_handle.state::<workspace::JobRegistry>().release(&job_id);
+    Ok(())
+}
+
+// 导出时选择清理版（默认）或口语清理前的逐字稿
+fn export_base(audio_file_path: &str, verbatim: Option<bool>) -> String {
+    if verbatim.unwrap_or(false) {
+        transcript::verbatim_base(audio_file_path)
+    } else {
+        audio_file_path.to_string()
+    }
+}
+
 #[tauri::command]
 async fn save_srt_file(
     _app_handle: tauri::AppHandle,
     audio_file_path: String,
     target_directory: String,
-) -> Result<String, String> {
+    verbatim: Option<bool>,
+) -> Result<String, MurmurError> {
     // 根据音频文件路径生成 SRT 文件路径
-    let srt_file_path = format!("{}.srt", audio_file_path);
+    let srt_file_path = format!("{}.srt", export_base(&audio_file_path, verbatim));
     let srt_path = std::path::Path::new(&srt_file_path);
     
     // 检查 SRT 文件是否存在
     if !srt_path.exists() {
-        return Err("未找到 SRT 字幕文件，请确保语音识别已完成".to_string());
+        return Err(MurmurError::TranscriptNotFound);
     }
     
     // 获取源文件名
     let file_name = srt_path.file_name()
         .and_then(|name| name.to_str())
-        .ok_or("无效的 SRT 文件名")?;
+        .ok_or_else(|| MurmurError::InvalidPath { path: srt_file_path.clone() })?;
     
     // 构建目标文件路径
     let target_path = std::path::Path::new(&target_directory).join(file_name);
@@ -1212,7 +2651,7 @@ async fn save_srt_file(
             let target_path_str = target_path.to_string_lossy().to_string();
             Ok(target_path_str)
         }
-        Err(e) => Err(format!("复制 SRT 文件失败: {}", e))
+        Err(e) => Err(MurmurError::io(&target_path, e))
     }
 }
 
@@ -1221,21 +2660,22 @@ async fn save_lrc_file(
     _app_handle: tauri::AppHandle,
     audio_file_path: String,
     target_directory: String,
-) -> Result<String, String> {
+    verbatim: Option<bool>,
+) -> Result<String, MurmurError> {
     // 根据音频文件路径生成 LRC 文件路径
-    let lrc_file_path = format!("{}.lrc", audio_file_path);
+    let lrc_file_path = format!("{}.lrc", export_base(&audio_file_path, verbatim));
     let lrc_path = std::path::Path::new(&lrc_file_path);
 
     // 检查 LRC 文件是否存在
     if !lrc_path.exists() {
-        return Err("未找到 LRC 歌词文件，请确保语音识别已完成并启用了 --output-lrc".to_string());
+        return Err(MurmurError::TranscriptNotFound);
     }
 
     // 获取源文件名
     let file_name = lrc_path
         .file_name()
         .and_then(|name| name.to_str())
-        .ok_or("无效的 LRC 文件名")?;
+        .ok_or_else(|| MurmurError::InvalidPath { path: lrc_file_path.clone() })?;
 
     // 构建目标文件路径
     let target_path = std::path::Path::new(&target_directory).join(file_name);
@@ -1246,14 +2686,269 @@ async fn save_lrc_file(
             let target_path_str = target_path.to_string_lossy().to_string();
             Ok(target_path_str)
         }
-        Err(e) => Err(format!("复制 LRC 文件失败: {}", e))
+        Err(e) => Err(MurmurError::io(&target_path, e))
     }
 }
 
+#[tauri::command]
+async fn save_txt_file(
+    _app_handle: tauri::AppHandle,
+    audio_file_path: String,
+    target_directory: String,
+    verbatim: Option<bool>,
+) -> Result<String, MurmurError> {
+    // 纯文本由后处理阶段根据转写结果生成
+    let txt_path = transcript::output_path(&export_base(&audio_file_path, verbatim), "txt");
+
+    if !txt_path.exists() {
+        return Err(MurmurError::TranscriptNotFound);
+    }
+
+    let file_name = txt_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| MurmurError::InvalidPath { path: txt_path.display().to_string() })?;
+
+    let target_path = std::path::Path::new(&target_directory).join(file_name);
+
+    match std::fs::copy(&txt_path, &target_path) {
+        Ok(_) => Ok(target_path.to_string_lossy().to_string()),
+        Err(e) => Err(MurmurError::io(&target_path, e)),
+    }
+}
+
+#[tauri::command]
+async fn get_transcript(
+    audio_file_path: String,
+    verbatim: Option<bool>,
+) -> Result<transcript::Transcript, MurmurError> {
+    let path = transcript::transcript_path(&export_base(&audio_file_path, verbatim));
+    if !path.exists() {
+        return Err(MurmurError::TranscriptNotFound);
+    }
+    Ok(transcript::load_transcript(&path)?)
+}
+
+// 波形峰值、响度与语音区间，供前端绘制时间轴；结果按参数缓存在音频旁
+#[tauri::command]
+async fn compute_audio_overview(
+    audio_file_path: String,
+    points: Option<usize>,
+    window_ms: Option<u32>,
+) -> Result<audio::AudioOverview, MurmurError> {
+    let points = points.unwrap_or(2000);
+    let window_ms = window_ms.unwrap_or(50).max(10);
+    tokio::task::spawn_blocking(move || {
+        audio::load_or_compute_overview(Path::new(&audio_file_path), points, window_ms)
+    })
+    .await
+    .map_err(|e| MurmurError::Internal { detail: e.to_string() })?
+    .map_err(MurmurError::from)
+}
+
+// 重命名某个任务中的说话人，同时更新逐字稿并重新生成导出文件
+#[tauri::command]
+async fn rename_speaker(
+    audio_file_path: String,
+    speaker_id: String,
+    name: String,
+) -> Result<transcript::Transcript, MurmurError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(MurmurError::SpeakerNameInvalid);
+    }
+    let path = transcript::transcript_path(&audio_file_path);
+    if !path.exists() {
+        return Err(MurmurError::TranscriptNotFound);
+    }
+
+    let verbatim_base = transcript::verbatim_base(&audio_file_path);
+    let mut updated = None;
+    for base in [audio_file_path.as_str(), verbatim_base.as_str()] {
+        let path = transcript::transcript_path(base);
+        if !path.exists() {
+            continue;
+        }
+        let mut result = transcript::load_transcript(&path)?;
+        if !result.speakers.contains_key(&speaker_id) {
+            return Err(MurmurError::SpeakerNotFound { id: speaker_id });
+        }
+        result.speakers.insert(speaker_id.clone(), name.clone());
+        transcript::save_transcript(&path, &result)?;
+        transcript::write_exports(base, &result)?;
+        updated.get_or_insert(result);
+    }
+    updated.ok_or(MurmurError::TranscriptNotFound)
+}
+
+// 通过 ffmpeg 读取媒体文件的全局标签（标题、艺术家、专辑），命令写入任务日志
+async fn probe_media_tags(
+    app_handle: &tauri::AppHandle,
+    media_path: &str,
+    log_path: &Path,
+) -> Result<karaoke::MediaTags, String> {
+    use tauri_plugin_shell::ShellExt;
+
+    let args = vec![
+        "-i".to_string(),
+        media_path.to_string(),
+        "-f".to_string(),
+        "ffmetadata".to_string(),
+        "-".to_string(),
+    ];
+    append_log_line(log_path, "CMD", &format_cmd_with_args("ffmpeg", &args));
+    let output = app_handle
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| format!("无法获取 ffmpeg sidecar: {}", e))?
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("无法执行 ffmpeg 命令: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("读取媒体标签失败: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(karaoke::parse_ffmetadata(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// 生成卡拉 OK 字幕：format 为 "lrc"（Enhanced LRC）或 "ass"；
+// media_path 为原始媒体文件，用于读取 [ti:]/[ar:]/[al:] 标签
+#[tauri::command]
+async fn save_karaoke_file(
+    app_handle: tauri::AppHandle,
+    audio_file_path: String,
+    target_directory: String,
+    format: String,
+    media_path: Option<String>,
+    offset_ms: Option<i64>,
+    max_line_chars: Option<usize>,
+) -> Result<String, MurmurError> {
+    let path = transcript::transcript_path(&audio_file_path);
+    if !path.exists() {
+        return Err(MurmurError::TranscriptNotFound);
+    }
+    let result = transcript::load_transcript(&path)?;
+
+    // 标签读取失败不影响导出，只是不写入头信息
+    let audio_p = Path::new(&audio_file_path);
+    let stem = audio_p.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let log_path = audio_p.parent().unwrap_or_else(|| Path::new(".")).join(format!("{}_log.txt", stem));
+    let tags = match media_path {
+        Some(p) => probe_media_tags(&app_handle, &p, &log_path).await.unwrap_or_else(|e| {
+            append_log_line(&log_path, "karaoke", &format!("读取媒体标签失败: {}", e));
+            karaoke::MediaTags::default()
+        }),
+        None => karaoke::MediaTags::default(),
+    };
+    let options = karaoke::KaraokeOptions {
+        offset_ms: offset_ms.unwrap_or(0),
+        max_line_chars: max_line_chars.unwrap_or(karaoke::DEFAULT_MAX_LINE_CHARS),
+        tags,
+    };
+    let content = match format.as_str() {
+        "lrc" => karaoke::render_enhanced_lrc(&result, &options),
+        "ass" => karaoke::render_ass(&result, &options),
+        other => return Err(MurmurError::UnsupportedKaraokeFormat { format: other.to_string() }),
+    };
+
+    let file_name = Path::new(&audio_file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MurmurError::InvalidPath { path: audio_file_path.clone() })?;
+    let target_path = Path::new(&target_directory).join(format!("{}.karaoke.{}", file_name, format));
+    std::fs::write(&target_path, content)
+        .map_err(|e| MurmurError::io(&target_path, e))?;
+    Ok(target_path.to_string_lossy().to_string())
+}
+
+// 列出低于置信度阈值的词，未指定阈值时使用设置中的值
+#[tauri::command]
+async fn get_low_confidence_words(
+    app_handle: tauri::AppHandle,
+    audio_file_path: String,
+    threshold: Option<f32>,
+) -> Result<Vec<transcript::UncertainWord>, MurmurError> {
+    let threshold = match threshold {
+        Some(t) => t,
+        None => load_settings(app_handle).await?.low_confidence_threshold,
+    };
+    let path = transcript::transcript_path(&audio_file_path);
+    if !path.exists() {
+        return Err(MurmurError::TranscriptNotFound);
+    }
+    let result = transcript::load_transcript(&path)?;
+    Ok(transcript::low_confidence_words(&result, threshold))
+}
+
+// 根据转写结果中记录的脱敏时间段生成消音后的音频副本
+#[tauri::command]
+async fn export_redacted_audio(
+    app_handle: tauri::AppHandle,
+    audio_file_path: String,
+    target_directory: String,
+    mode: Option<String>,
+) -> Result<String, MurmurError> {
+    use tauri_plugin_shell::ShellExt;
+
+    let settings = load_settings(app_handle.clone()).await?;
+    let mode_name = mode.unwrap_or(settings.redaction_audio_mode);
+    let audio_mode = redaction::AudioMode::from_setting(&mode_name)
+        .ok_or_else(|| MurmurError::UnsupportedRedactionMode { mode: mode_name.clone() })?;
+
+    let path = transcript::transcript_path(&audio_file_path);
+    if !path.exists() {
+        return Err(MurmurError::TranscriptNotFound);
+    }
+    let result = transcript::load_transcript(&path)?;
+
+    let audio_p = PathBuf::from(&audio_file_path);
+    let stem = audio_p.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let target_path = Path::new(&target_directory).join(format!("{}.redacted.wav", stem));
+    let log_path = audio_p.parent().unwrap_or_else(|| Path::new(".")).join(format!("{}_log.txt", stem));
+
+    let args = redaction::ffmpeg_args(
+        &audio_file_path,
+        &target_path.to_string_lossy(),
+        &result.redactions,
+        audio_mode,
+    );
+    let ffmpeg_sidecar = app_handle
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| MurmurError::SidecarUnavailable { name: "ffmpeg".to_string(), detail: e.to_string() })?;
+    let cmd_str = format_cmd_with_args("ffmpeg", &args);
+    append_log_line(&log_path, "CMD", &cmd_str);
+
+    let output = run_sidecar(ffmpeg_sidecar.args(&args), settings.background_priority, &log_path)
+        .await
+        .map_err(|detail| MurmurError::FfmpegSpawn { detail })?;
+    let err_str = String::from_utf8_lossy(&output.stderr);
+    for l in err_str.lines() { append_log_line(&log_path, "ffmpeg:stderr", l); }
+    if !output.success() {
+        return Err(MurmurError::FfmpegFailed { detail: err_str.to_string() });
+    }
+    append_log_line(&log_path, "redaction", &format!("已生成脱敏音频: {} 个区间", result.redactions.len()));
+    Ok(target_path.to_string_lossy().to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let args: Vec<String> = std::env::args().collect();
+    let portable_env = std::env::var(paths::PORTABLE_ENV).ok();
+    let exe = std::env::current_exe().ok();
+    let portable = paths::detect(&args, portable_env.as_deref(), exe.as_deref());
+    // 便携数据目录创建失败时没有可写的日志位置，只能输出到 stderr
+    if let Some(p) = &portable {
+        match paths::prepare(p) {
+            Ok(()) => app_log(&p.dir, "portable", &format!("便携模式，数据目录: {}", p.dir.display())),
+            Err(e) => eprintln!("创建便携数据目录失败: {}: {}", p.dir.display(), e),
+        }
+    }
+
     tauri::Builder::default()
+        .manage(paths::DataRoot(portable))
         .manage(WhisperProcState::default())
+        .manage(workspace::JobRegistry::default())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
@@ -1265,16 +2960,43 @@ pub fn run() {
             get_file_info,
             process_media_file_from_path,
             save_settings, 
+            validate_settings,
+            list_profiles,
+            create_profile,
+            rename_profile,
+            duplicate_profile,
+            delete_profile,
+            activate_profile,
+            export_config,
+            import_config,
+            get_managed_config,
             load_settings,
+            get_vad_presets,
             start_whisper_recognition,
             stop_whisper_recognition,
+            pause_job,
+            resume_job,
+            get_whisper_status,
+            cleanup_jobs,
+            release_job,
+            list_cache_entries,
+            evict_cache,
             check_model_exists,
             check_coreml_support,
             save_srt_file,
             save_lrc_file,
+            save_txt_file,
+            get_transcript,
+            compute_audio_overview,
+            rename_speaker,
+            save_karaoke_file,
+            get_low_confidence_words,
+            export_redacted_audio,
             get_app_data_info,
+            clean_app_data,
             open_app_data_directory,
             get_system_info_command,
+            get_thread_info,
             get_vulkan_support,
             list_downloaded_models
         ])
@@ -1315,10 +3037,11 @@ mod tests {
         fs::create_dir_all(&coreml_path).expect("Failed to create CoreML directory");
         
         // 模拟应用设置
-        let settings = AppSettings {
+        let _settings = AppSettings {
             whisper_models_path: Some(model_dir),
             whisper_language: "auto".to_string(),
             whisper_model: model_name.clone(),
+            ..Default::default()
         };
         
         // 由于我们无法在单元测试中创建真实的 Tauri AppHandle，
@@ -1340,10 +3063,11 @@ mod tests {
         let coreml_path = Path::new(&model_dir).join(format!("{}.mlmodelc", model_stem));
         
         // 模拟应用设置
-        let settings = AppSettings {
+        let _settings = AppSettings {
             whisper_models_path: Some(model_dir),
             whisper_language: "auto".to_string(),
             whisper_model: model_name.clone(),
+            ..Default::default()
         };
         
         // 检查 CoreML 路径不存在
@@ -1369,6 +3093,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_tinydiarize_model() {
+        assert!(is_tinydiarize_model("ggml-small.en-tdrz.bin"));
+        assert!(!is_tinydiarize_model("ggml-small.en.bin"));
+    }
+
+    #[test]
+    fn test_dtw_preset() {
+        assert_eq!(dtw_preset("ggml-tiny-q5_1.bin").as_deref(), Some("tiny"));
+        assert_eq!(dtw_preset("ggml-base.en.bin").as_deref(), Some("base.en"));
+        assert_eq!(dtw_preset("ggml-large-v3-turbo-q5_0.bin").as_deref(), Some("large.v3.turbo"));
+        assert_eq!(dtw_preset("my-finetune.bin"), None);
+    }
+
+    #[test]
+    fn test_extract_detected_language() {
+        assert_eq!(extract_detected_language("whisper_full_with_state: auto-detected language: ja (p = 0.973)"), Some("ja"));
+        assert_eq!(extract_detected_language("whisper_print_progress_callback: progress = 10%"), None);
+    }
+
+    #[test]
+    fn test_result_key_ignores_runtime_settings() {
+        let model = Path::new("/nonexistent/ggml-base.bin");
+        let base = AppSettings::default();
+        let key = result_key("input", &base, model);
+
+        let faster = AppSettings { thread_count: 8, disable_gpu: true, ..AppSettings::default() };
+        assert_eq!(result_key("input", &faster, model), key);
+
+        let other_language = AppSettings { whisper_language: "zh".to_string(), ..AppSettings::default() };
+        assert_ne!(result_key("input", &other_language, model), key);
+        assert_ne!(result_key("other", &base, model), key);
+    }
+
+    #[test]
+    fn test_normalize_settings() {
+        let mut settings = AppSettings {
+            whisper_models_path: Some("\"file:///nonexistent/models/\"".to_string()),
+            thread_count: 64,
+            whisper_optimization: "metal".to_string(),
+            low_confidence_threshold: 1.5,
+            ..AppSettings::default()
+        };
+        let report = normalize_settings(&mut settings.clone(), false);
+        let fields: Vec<&str> = report.errors.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, vec!["whisper_optimization", "low_confidence_threshold"]);
+
+        // 读取时不合法的字段恢复为默认值，只产生警告
+        let report = normalize_settings(&mut settings, true);
+        assert!(report.is_ok());
+        assert_eq!(settings.whisper_models_path.as_deref(), Some("/nonexistent/models"));
+        assert_eq!(settings.thread_count, cpu::logical_cores().min(64));
+        assert_eq!(settings.whisper_optimization, "none");
+        assert_eq!(settings.low_confidence_threshold, 0.5);
+
+        // 说话人聚类需要声纹模型，未指定模型或未编译该功能时不能启用
+        let mut settings = AppSettings { speaker_clustering: true, ..AppSettings::default() };
+        let report = normalize_settings(&mut settings.clone(), false);
+        let field = if cfg!(feature = "diarization") { "speaker_model_path" } else { "speaker_clustering" };
+        assert_eq!(report.errors[0].field, field);
+        normalize_settings(&mut settings, true);
+        assert!(!settings.speaker_clustering);
+    }
+
     #[test]
     fn test_coreml_path_construction() {
         let model_dir = "/path/to/models";
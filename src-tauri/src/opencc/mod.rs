@@ -0,0 +1,215 @@
+// 简繁转换：参照 OpenCC 的词典格式与转换链，词典以文本形式内嵌
+// 词典格式：每行 "键<TAB>候选1 候选2 ..."，取第一个候选作为默认结果
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const ST_CHARACTERS: &str = include_str!("STCharacters.txt");
+const ST_PHRASES: &str = include_str!("STPhrases.txt");
+const TS_PHRASES: &str = include_str!("TSPhrases.txt");
+const TW_VARIANTS: &str = include_str!("TWVariants.txt");
+const HK_VARIANTS: &str = include_str!("HKVariants.txt");
+const VARIANTS_REV_PHRASES: &str = include_str!("VariantsRevPhrases.txt");
+
+// 输出字形目标，对应设置项 output_script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScriptTarget {
+    Hans,
+    Hant,
+    Tw,
+    Hk,
+}
+
+impl ScriptTarget {
+    // "keep" 或未知取值返回 None，表示保持 whisper 原始输出
+    pub(crate) fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "zh-Hans" => Some(Self::Hans),
+            "zh-Hant" => Some(Self::Hant),
+            "zh-TW" => Some(Self::Tw),
+            "zh-HK" => Some(Self::Hk),
+            _ => None,
+        }
+    }
+}
+
+// 只转换中文（含粤语）转写；日文汉字等其他语言的 CJK 文本不能按简繁词典改写
+pub(crate) fn applies_to(language: &str) -> bool {
+    let primary = language.split(['-', '_']).next().unwrap_or(language);
+    primary.eq_ignore_ascii_case("zh") || primary.eq_ignore_ascii_case("yue")
+}
+
+// 单个词典：最大正向匹配
+struct Dict {
+    map: HashMap<String, String>,
+    max_len: usize,
+}
+
+impl Dict {
+    fn new() -> Self {
+        Self { map: HashMap::new(), max_len: 1 }
+    }
+
+    // 读取 OpenCC 格式文本；已存在的键不覆盖，便于先载入词组再载入单字
+    fn load(&mut self, data: &str) {
+        for line in data.lines() {
+            let mut parts = line.splitn(2, '\t');
+            let key = match parts.next() {
+                Some(k) if !k.is_empty() => k,
+                _ => continue,
+            };
+            let value = match parts.next().and_then(|v| v.split_whitespace().next()) {
+                Some(v) => v,
+                None => continue,
+            };
+            self.insert(key, value);
+        }
+    }
+
+    // 反转 OpenCC 格式文本（例如由 STCharacters 得到繁转简单字表）
+    fn load_reversed(&mut self, data: &str) {
+        for line in data.lines() {
+            let mut parts = line.splitn(2, '\t');
+            let (key, values) = match (parts.next(), parts.next()) {
+                (Some(k), Some(v)) if !k.is_empty() => (k, v),
+                _ => continue,
+            };
+            for v in values.split_whitespace() {
+                if v != key {
+                    self.insert(v, key);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, key: &str, value: &str) {
+        if self.map.contains_key(key) {
+            return;
+        }
+        self.max_len = self.max_len.max(key.chars().count());
+        self.map.insert(key.to_string(), value.to_string());
+    }
+
+    fn convert(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let mut matched = false;
+            let upper = self.max_len.min(chars.len() - i);
+            for len in (1..=upper).rev() {
+                let key: String = chars[i..i + len].iter().collect();
+                if let Some(v) = self.map.get(&key) {
+                    out.push_str(v);
+                    i += len;
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+        out
+    }
+}
+
+// 各阶段词典只构建一次
+fn variants_rev() -> &'static Dict {
+    static D: OnceLock<Dict> = OnceLock::new();
+    D.get_or_init(|| {
+        // 先载入阻断词组（如 "著名"），避免 著 被统一为 着
+        let mut d = Dict::new();
+        d.load(VARIANTS_REV_PHRASES);
+        d.load_reversed(TW_VARIANTS);
+        d.load_reversed(HK_VARIANTS);
+        d
+    })
+}
+
+fn traditional_to_simplified() -> &'static Dict {
+    static D: OnceLock<Dict> = OnceLock::new();
+    D.get_or_init(|| {
+        let mut d = Dict::new();
+        d.load(TS_PHRASES);
+        d.load_reversed(ST_CHARACTERS);
+        d
+    })
+}
+
+fn simplified_to_traditional() -> &'static Dict {
+    static D: OnceLock<Dict> = OnceLock::new();
+    D.get_or_init(|| {
+        let mut d = Dict::new();
+        d.load(ST_PHRASES);
+        d.load(ST_CHARACTERS);
+        d
+    })
+}
+
+fn tw_variants() -> &'static Dict {
+    static D: OnceLock<Dict> = OnceLock::new();
+    D.get_or_init(|| {
+        let mut d = Dict::new();
+        d.load(TW_VARIANTS);
+        d
+    })
+}
+
+fn hk_variants() -> &'static Dict {
+    static D: OnceLock<Dict> = OnceLock::new();
+    D.get_or_init(|| {
+        let mut d = Dict::new();
+        d.load(HK_VARIANTS);
+        d
+    })
+}
+
+// 转换链：先统一异体字，再转为简体；繁体目标在简体基础上再转换，
+// 这样 whisper 输出的繁简混排文本不会被重复转换（例如 系統 -> 係統）
+pub(crate) fn convert(text: &str, target: ScriptTarget) -> String {
+    let normalized = variants_rev().convert(text);
+    let simplified = traditional_to_simplified().convert(&normalized);
+    match target {
+        ScriptTarget::Hans => simplified,
+        ScriptTarget::Hant => simplified_to_traditional().convert(&simplified),
+        ScriptTarget::Tw => tw_variants().convert(&simplified_to_traditional().convert(&simplified)),
+        ScriptTarget::Hk => hk_variants().convert(&simplified_to_traditional().convert(&simplified)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_target_from_setting() {
+        assert_eq!(ScriptTarget::from_setting("zh-Hans"), Some(ScriptTarget::Hans));
+        assert_eq!(ScriptTarget::from_setting("zh-HK"), Some(ScriptTarget::Hk));
+        assert_eq!(ScriptTarget::from_setting("keep"), None);
+        assert!(applies_to("zh") && applies_to("zh-TW") && applies_to("yue"));
+        assert!(!applies_to("ja") && !applies_to("auto"));
+    }
+
+    #[test]
+    fn test_traditional_to_simplified() {
+        assert_eq!(convert("我們今天開會討論這個問題", ScriptTarget::Hans), "我们今天开会讨论这个问题");
+        // 词组优先，且异体字先统一
+        assert_eq!(convert("乾隆皇帝著名的詩裡", ScriptTarget::Hans), "乾隆皇帝著名的诗里");
+    }
+
+    #[test]
+    fn test_simplified_to_traditional_phrases() {
+        assert_eq!(convert("头发干净", ScriptTarget::Hant), "頭髮乾淨");
+        assert_eq!(convert("以后的系统", ScriptTarget::Hant), "以後的系統");
+        // 繁体输入经过往返转换保持稳定
+        assert_eq!(convert("以後的系統", ScriptTarget::Hant), "以後的系統");
+    }
+
+    #[test]
+    fn test_regional_variants() {
+        assert_eq!(convert("为了里面的人", ScriptTarget::Tw), "為了裡面的人");
+        assert_eq!(convert("说明线路", ScriptTarget::Hk), "説明綫路");
+        assert_eq!(convert("English stays", ScriptTarget::Tw), "English stays");
+    }
+}
@@ -0,0 +1,115 @@
+// 数据目录的位置：默认为系统的应用数据目录；便携模式下放在可执行文件旁边，不向用户目录写入任何内容。
+// 启用便携模式（优先级从高到低）：命令行参数 --portable[=<目录>]、环境变量 MURMUR_PORTABLE、
+// 可执行文件旁的 portable 标记文件。macOS 上以 .app 所在目录为准
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+pub(crate) const PORTABLE_FLAG: &str = "--portable";
+pub(crate) const PORTABLE_ENV: &str = "MURMUR_PORTABLE";
+pub(crate) const PORTABLE_MARKER: &str = "portable";
+// 便携模式默认的数据目录名
+pub(crate) const PORTABLE_DATA_DIR: &str = "MurMurData";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PortableSource {
+    Flag,
+    Env,
+    Marker,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Portable {
+    pub(crate) dir: PathBuf,
+    pub(crate) source: PortableSource,
+}
+
+// 启动时确定的数据目录，None 表示使用系统的应用数据目录
+#[derive(Debug, Default)]
+pub(crate) struct DataRoot(pub(crate) Option<Portable>);
+
+// 可执行文件所在目录；位于 .app/Contents/MacOS 中时取 .app 所在目录
+fn base_dir(exe: &Path) -> Option<PathBuf> {
+    let dir = exe.parent()?;
+    if dir.ends_with("Contents/MacOS") {
+        if let Some(bundle_parent) = dir.parent().and_then(Path::parent).and_then(Path::parent) {
+            return Some(bundle_parent.to_path_buf());
+        }
+    }
+    Some(dir.to_path_buf())
+}
+
+// 相对路径相对于可执行文件所在目录；空值使用默认目录
+fn data_dir(base: &Path, custom: Option<&str>) -> PathBuf {
+    match custom.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(custom) => base.join(custom),
+        None => base.join(PORTABLE_DATA_DIR),
+    }
+}
+
+pub(crate) fn detect(args: &[String], env: Option<&str>, exe: Option<&Path>) -> Option<Portable> {
+    let base = base_dir(exe?)?;
+
+    for arg in args.iter().skip(1) {
+        if arg == PORTABLE_FLAG {
+            return Some(Portable { dir: data_dir(&base, None), source: PortableSource::Flag });
+        }
+        if let Some(custom) = arg.strip_prefix(PORTABLE_FLAG).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(Portable { dir: data_dir(&base, Some(custom)), source: PortableSource::Flag });
+        }
+    }
+
+    // 环境变量为 0/false/off 时显式关闭，忽略标记文件；1/true/on 使用默认目录，其他值视为目录
+    if let Some(value) = env.map(str::trim).filter(|v| !v.is_empty()) {
+        return match value.to_ascii_lowercase().as_str() {
+            "0" | "false" | "off" | "no" => None,
+            "1" | "true" | "on" | "yes" => Some(Portable { dir: data_dir(&base, None), source: PortableSource::Env }),
+            _ => Some(Portable { dir: data_dir(&base, Some(value)), source: PortableSource::Env }),
+        };
+    }
+
+    base.join(PORTABLE_MARKER)
+        .is_file()
+        .then(|| Portable { dir: data_dir(&base, None), source: PortableSource::Marker })
+}
+
+// 在创建窗口前调用：建立数据目录，并让 WebView2（Windows）的用户数据也写入其中。
+// macOS 与 Linux 的系统 WebView 数据位置无法由应用指定
+pub(crate) fn prepare(portable: &Portable) -> std::io::Result<()> {
+    std::fs::create_dir_all(&portable.dir)?;
+    if cfg!(target_os = "windows") {
+        std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", portable.dir.join("webview"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect() {
+        let dir = TempDir::new().unwrap();
+        let exe = dir.path().join("murmur");
+        let args = |extra: &[&str]| {
+            std::iter::once("murmur").chain(extra.iter().copied()).map(String::from).collect::<Vec<_>>()
+        };
+
+        assert_eq!(detect(&args(&[]), None, Some(&exe)), None);
+        let flag = detect(&args(&["--portable"]), None, Some(&exe)).unwrap();
+        assert_eq!((flag.dir, flag.source), (dir.path().join(PORTABLE_DATA_DIR), PortableSource::Flag));
+        let custom = detect(&args(&["--portable=data"]), Some("0"), Some(&exe)).unwrap();
+        assert_eq!(custom.dir, dir.path().join("data"));
+
+        let env = detect(&args(&[]), Some("/mnt/usb/murmur"), Some(&exe)).unwrap();
+        assert_eq!((env.dir, env.source), (PathBuf::from("/mnt/usb/murmur"), PortableSource::Env));
+
+        std::fs::write(dir.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(detect(&args(&[]), None, Some(&exe)).unwrap().source, PortableSource::Marker);
+        assert_eq!(detect(&args(&[]), Some("false"), Some(&exe)), None);
+
+        let app = dir.path().join("MurMur.app/Contents/MacOS/murmur");
+        assert_eq!(detect(&args(&[]), None, Some(&app)).unwrap().dir, dir.path().join(PORTABLE_DATA_DIR));
+    }
+}
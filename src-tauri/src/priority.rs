@@ -0,0 +1,365 @@
+// 后台识别：降低 whisper-cli / ffmpeg 子进程的调度优先级，并在其他程序占满 CPU 时暂停识别进程、负载回落后恢复。
+// Linux 使用 nice + ionice，macOS 使用 nice，Windows 使用 BELOW_NORMAL 优先级；暂停/恢复在 Unix 上为 SIGSTOP/SIGCONT，
+// Windows 上逐个挂起/恢复进程的线程。用户也可以手动暂停/恢复，暂停期间的时长不计入剩余时间估算
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+// 后台优先级使用的 nice 值
+#[cfg(unix)]
+const NICE: i32 = 10;
+
+// 负载采样间隔
+pub(crate) const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+// 暂停后负载需回落到上限以下这么多（百分点）才恢复，避免频繁切换
+const RESUME_MARGIN: f32 = 10.0;
+
+// 以下函数返回操作系统的原始错误，说明文字由调用方写入日志或转换为 MurmurError
+#[cfg(unix)]
+pub(crate) fn lower_priority(pid: u32) -> std::io::Result<()> {
+    // SAFETY: 只修改指定子进程的调度参数，不涉及内存
+    let ret = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, NICE) };
+    if ret != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // I/O 优先级：best-effort 类中的最低级别（7），不使用 idle 类以免磁盘繁忙时完全停滞
+    #[cfg(target_os = "linux")]
+    {
+        const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+        const IOPRIO_CLASS_BE: libc::c_long = 2;
+        const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+        let value = (IOPRIO_CLASS_BE << IOPRIO_CLASS_SHIFT) | 7;
+        // SAFETY: ioprio_set 只读取这三个整数参数
+        let ret = unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid as libc::c_long, value) };
+        if ret != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn signal(pid: u32, sig: libc::c_int) -> std::io::Result<()> {
+    // SAFETY: 向指定子进程发送信号
+    let ret = unsafe { libc::kill(pid as libc::pid_t, sig) };
+    if ret != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(crate) fn suspend(pid: u32) -> std::io::Result<()> {
+    signal(pid, libc::SIGSTOP)
+}
+
+#[cfg(unix)]
+pub(crate) fn resume(pid: u32) -> std::io::Result<()> {
+    signal(pid, libc::SIGCONT)
+}
+
+#[cfg(windows)]
+pub(crate) fn lower_priority(pid: u32) -> std::io::Result<()> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, SetPriorityClass, BELOW_NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+    };
+    // SAFETY: 句柄在使用后立即关闭
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        let ok = SetPriorityClass(handle, BELOW_NORMAL_PRIORITY_CLASS);
+        CloseHandle(handle);
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+// Windows 没有公开的整进程挂起接口，逐个挂起/恢复属于该进程的线程
+#[cfg(windows)]
+fn for_each_thread(pid: u32, suspend: bool) -> std::io::Result<()> {
+    use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows_sys::Win32::System::Threading::{OpenThread, ResumeThread, SuspendThread, THREAD_SUSPEND_RESUME};
+    // SAFETY: 快照与线程句柄在使用后立即关闭，THREADENTRY32 按要求设置 dwSize
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err(std::io::Error::last_os_error());
+        }
+        let mut entry: THREADENTRY32 = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<THREADENTRY32>() as u32;
+        let mut more = Thread32First(snapshot, &mut entry);
+        while more != 0 {
+            if entry.th32OwnerProcessID == pid {
+                let thread = OpenThread(THREAD_SUSPEND_RESUME, 0, entry.th32ThreadID);
+                if !thread.is_null() {
+                    if suspend {
+                        SuspendThread(thread);
+                    } else {
+                        ResumeThread(thread);
+                    }
+                    CloseHandle(thread);
+                }
+            }
+            more = Thread32Next(snapshot, &mut entry);
+        }
+        CloseHandle(snapshot);
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub(crate) fn suspend(pid: u32) -> std::io::Result<()> {
+    for_each_thread(pid, true)
+}
+
+#[cfg(windows)]
+pub(crate) fn resume(pid: u32) -> std::io::Result<()> {
+    for_each_thread(pid, false)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn lower_priority(_pid: u32) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn suspend(_pid: u32) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn resume(_pid: u32) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+// 识别进程的调度状态，随事件发送给前端
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ThrottleStatus {
+    pub(crate) background_priority: bool,
+    // 0 表示不限制
+    pub(crate) cpu_cap_percent: u32,
+    // 因系统繁忙被自动暂停
+    pub(crate) paused: bool,
+    // 被用户手动暂停
+    pub(crate) user_paused: bool,
+    // 除识别进程外的 CPU 占用，占全部核心的百分比
+    pub(crate) other_load_percent: f32,
+    // 累计暂停的秒数
+    pub(crate) paused_seconds: f64,
+}
+
+impl ThrottleStatus {
+    pub(crate) fn new(background_priority: bool, cpu_cap_percent: u32) -> Self {
+        Self {
+            background_priority,
+            cpu_cap_percent,
+            paused: false,
+            user_paused: false,
+            other_load_percent: 0.0,
+            paused_seconds: 0.0,
+        }
+    }
+
+    // 进程是否处于挂起状态
+    pub(crate) fn stopped(&self) -> bool {
+        self.paused || self.user_paused
+    }
+}
+
+// 只统计进程实际运行的时长，用于估算剩余时间
+#[derive(Debug, Clone)]
+pub(crate) struct ActiveClock {
+    started: Instant,
+    paused_since: Option<Instant>,
+    paused_total: Duration,
+}
+
+impl ActiveClock {
+    pub(crate) fn new(now: Instant) -> Self {
+        Self { started: now, paused_since: None, paused_total: Duration::ZERO }
+    }
+
+    pub(crate) fn pause(&mut self, now: Instant) {
+        self.paused_since.get_or_insert(now);
+    }
+
+    pub(crate) fn resume(&mut self, now: Instant) {
+        if let Some(since) = self.paused_since.take() {
+            self.paused_total += now.saturating_duration_since(since);
+        }
+    }
+
+    pub(crate) fn paused(&self, now: Instant) -> Duration {
+        self.paused_total + self.paused_since.map_or(Duration::ZERO, |since| now.saturating_duration_since(since))
+    }
+
+    pub(crate) fn active(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.started).saturating_sub(self.paused(now))
+    }
+
+    // 按已运行时长与进度线性估算剩余秒数，进度过小时不估算
+    pub(crate) fn eta(&self, percentage: f64, now: Instant) -> Option<f64> {
+        if !(1.0..100.0).contains(&percentage) {
+            return None;
+        }
+        Some(self.active(now).as_secs_f64() * (100.0 - percentage) / percentage)
+    }
+}
+
+// 一个识别进程的暂停状态：自动暂停与手动暂停相互独立，任一生效时进程保持挂起，两者都解除后才恢复
+pub(crate) struct JobControl {
+    pid: u32,
+    status: ThrottleStatus,
+    clock: ActiveClock,
+}
+
+impl JobControl {
+    pub(crate) fn new(pid: u32, status: ThrottleStatus) -> Self {
+        Self { pid, status, clock: ActiveClock::new(Instant::now()) }
+    }
+
+    // 按新的暂停状态挂起或恢复进程，挂起状态不变时不发送信号
+    fn apply(&mut self, next: ThrottleStatus) -> std::io::Result<()> {
+        let now = Instant::now();
+        match (self.status.stopped(), next.stopped()) {
+            (false, true) => {
+                suspend(self.pid)?;
+                self.clock.pause(now);
+            }
+            (true, false) => {
+                resume(self.pid)?;
+                self.clock.resume(now);
+            }
+            _ => {}
+        }
+        self.status = next;
+        Ok(())
+    }
+
+    pub(crate) fn set_user_paused(&mut self, paused: bool) -> std::io::Result<()> {
+        let next = ThrottleStatus { user_paused: paused, ..self.status.clone() };
+        self.apply(next)
+    }
+
+    pub(crate) fn set_throttled(&mut self, paused: bool, other_load_percent: f32) -> std::io::Result<()> {
+        let next = ThrottleStatus { paused, other_load_percent, ..self.status.clone() };
+        self.apply(next)
+    }
+
+    pub(crate) fn eta(&self, percentage: f64) -> Option<f64> {
+        self.clock.eta(percentage, Instant::now())
+    }
+
+    pub(crate) fn snapshot(&self) -> ThrottleStatus {
+        ThrottleStatus { paused_seconds: self.clock.paused(Instant::now()).as_secs_f64(), ..self.status.clone() }
+    }
+}
+
+// 按其他程序的负载决定暂停或恢复；只统计其他程序，识别进程自身的占用不会触发暂停
+pub(crate) struct Throttle {
+    cap: f32,
+    paused: bool,
+}
+
+impl Throttle {
+    pub(crate) fn new(cap_percent: u32) -> Self {
+        Self { cap: cap_percent as f32, paused: false }
+    }
+
+    // 返回 Some(true) 表示应暂停，Some(false) 表示应恢复，None 表示保持
+    pub(crate) fn update(&mut self, other_load: f32) -> Option<bool> {
+        let next = if self.paused { other_load > self.cap - RESUME_MARGIN } else { other_load > self.cap };
+        (next != self.paused).then(|| {
+            self.paused = next;
+            next
+        })
+    }
+}
+
+// 系统总占用减去识别进程的占用；进程占用按单核百分比计，需按核心数折算
+pub(crate) fn other_load(total_percent: f32, process_percent: f32, cores: usize) -> f32 {
+    (total_percent - process_percent / cores.max(1) as f32).clamp(0.0, 100.0)
+}
+
+pub(crate) struct LoadSampler {
+    sys: System,
+    pid: Pid,
+}
+
+impl LoadSampler {
+    pub(crate) fn new(pid: u32) -> Self {
+        let mut sampler = Self { sys: System::new(), pid: Pid::from_u32(pid) };
+        // CPU 占用按两次采样的差值计算，先采一次作为基准
+        sampler.sample();
+        sampler
+    }
+
+    pub(crate) fn sample(&mut self) -> f32 {
+        self.sys.refresh_cpu_usage();
+        self.sys
+            .refresh_processes_specifics(ProcessesToUpdate::Some(&[self.pid]), true, ProcessRefreshKind::new().with_cpu());
+        let process = self.sys.process(self.pid).map_or(0.0, |p| p.cpu_usage());
+        other_load(self.sys.global_cpu_usage(), process, self.sys.cpus().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_hysteresis() {
+        let mut throttle = Throttle::new(70);
+        assert_eq!(throttle.update(50.0), None);
+        assert_eq!(throttle.update(85.0), Some(true));
+        assert_eq!(throttle.update(65.0), None);
+        assert_eq!(throttle.update(55.0), Some(false));
+        assert_eq!(other_load(60.0, 400.0, 8), 10.0);
+        assert_eq!(other_load(10.0, 400.0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_active_clock() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut clock = ActiveClock::new(start);
+        assert_eq!(clock.eta(0.5, at(10)), None);
+        assert_eq!(clock.eta(25.0, at(10)), Some(30.0));
+        // 暂停的 20 秒不计入
+        clock.pause(at(10));
+        clock.pause(at(15));
+        clock.resume(at(30));
+        assert_eq!(clock.paused(at(40)), Duration::from_secs(20));
+        assert_eq!(clock.eta(50.0, at(40)), Some(20.0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_signals_on_child() {
+        let mut child = std::process::Command::new("sleep").arg("5").spawn().unwrap();
+        let pid = child.id();
+        lower_priority(pid).unwrap();
+        suspend(pid).unwrap();
+        resume(pid).unwrap();
+
+        // 手动暂停期间负载回落，不应恢复进程
+        let mut control = JobControl::new(pid, ThrottleStatus::new(false, 50));
+        control.set_user_paused(true).unwrap();
+        control.set_throttled(true, 90.0).unwrap();
+        control.set_throttled(false, 10.0).unwrap();
+        assert!(control.snapshot().stopped());
+        control.set_user_paused(false).unwrap();
+        assert!(!control.snapshot().stopped());
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+}
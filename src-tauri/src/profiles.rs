@@ -0,0 +1,169 @@
+// 命名配置方案：保存一组识别相关的设置（模型、加速方式、线程、VAD 等），可随时切换。
+// 全部方案保存在应用数据目录下的 profiles.json；方案中的设置是部分字段，激活时覆盖到当前设置上
+use crate::error::MurmurError;
+use crate::settings_file::write_atomic;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+pub(crate) const PROFILES_FILE: &str = "profiles.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct Profile {
+    pub(crate) name: String,
+    pub(crate) settings: Map<String, Value>,
+    pub(crate) created_at: u64,
+    pub(crate) updated_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct ProfileStore {
+    // 最近一次激活的方案；之后手动修改设置不会清除
+    #[serde(default)]
+    pub(crate) active: Option<String>,
+    #[serde(default)]
+    pub(crate) profiles: Vec<Profile>,
+}
+
+// 把 overrides 合并到 base 上：对象逐层合并，其他类型整体替换；excluded 中的顶层字段不覆盖
+pub(crate) fn overlay(base: &mut Map<String, Value>, overrides: &Map<String, Value>, excluded: &[&str]) {
+    for (key, value) in overrides {
+        if excluded.contains(&key.as_str()) {
+            continue;
+        }
+        match (base.get_mut(key), value) {
+            (Some(Value::Object(target)), Value::Object(partial)) => overlay(target, partial, &[]),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+// 去掉不属于方案的字段（本机路径、界面语言、存储策略等）
+pub(crate) fn strip(settings: &mut Map<String, Value>, excluded: &[&str]) {
+    settings.retain(|key, _| !excluded.contains(&key.as_str()));
+}
+
+fn check_name(name: &str) -> Result<String, MurmurError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(MurmurError::ProfileNameInvalid);
+    }
+    Ok(name.to_string())
+}
+
+impl ProfileStore {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(PROFILES_FILE)
+    }
+
+    pub(crate) fn load(dir: &Path) -> Result<Self, MurmurError> {
+        let path = Self::path(dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path).map_err(|e| MurmurError::io(&path, e))?;
+        serde_json::from_str(&content).map_err(|e| MurmurError::io(&path, e))
+    }
+
+    pub(crate) fn save(&self, dir: &Path) -> Result<(), MurmurError> {
+        let path = Self::path(dir);
+        let json = serde_json::to_string_pretty(self).map_err(|e| MurmurError::Internal { detail: e.to_string() })?;
+        std::fs::create_dir_all(dir).map_err(|e| MurmurError::io(dir, e))?;
+        write_atomic(&path, json.as_bytes(), || {}).map_err(|e| MurmurError::io(&path, e))
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Result<&Profile, MurmurError> {
+        self.profiles
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| MurmurError::ProfileNotFound { name: name.to_string() })
+    }
+
+    fn ensure_free(&self, name: &str) -> Result<(), MurmurError> {
+        if self.profiles.iter().any(|p| p.name == name) {
+            return Err(MurmurError::ProfileExists { name: name.to_string() });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn create(&mut self, name: &str, settings: Map<String, Value>, now: u64) -> Result<&Profile, MurmurError> {
+        let name = check_name(name)?;
+        self.ensure_free(&name)?;
+        self.profiles.push(Profile { name, settings, created_at: now, updated_at: now });
+        Ok(self.profiles.last().expect("刚添加的方案"))
+    }
+
+    pub(crate) fn rename(&mut self, name: &str, new_name: &str, now: u64) -> Result<(), MurmurError> {
+        let new_name = check_name(new_name)?;
+        self.get(name)?;
+        if new_name != name {
+            self.ensure_free(&new_name)?;
+        }
+        if let Some(profile) = self.profiles.iter_mut().find(|p| p.name == name) {
+            profile.name = new_name.clone();
+            profile.updated_at = now;
+        }
+        if self.active.as_deref() == Some(name) {
+            self.active = Some(new_name);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn duplicate(&mut self, name: &str, new_name: &str, now: u64) -> Result<&Profile, MurmurError> {
+        let settings = self.get(name)?.settings.clone();
+        self.create(new_name, settings, now)
+    }
+
+    pub(crate) fn delete(&mut self, name: &str) -> Result<(), MurmurError> {
+        self.get(name)?;
+        self.profiles.retain(|p| p.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn map(json: &str) -> Map<String, Value> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut base = map(r#"{"whisper_model":"tiny","thread_count":4,"app_locale":"en","vad":{"threshold":0.5,"speech_pad_ms":30}}"#);
+        overlay(&mut base, &map(r#"{"thread_count":8,"app_locale":"zh-CN","vad":{"threshold":0.6}}"#), &["app_locale"]);
+        assert_eq!(
+            Value::Object(base),
+            serde_json::json!({"whisper_model":"tiny","thread_count":8,"app_locale":"en","vad":{"threshold":0.6,"speech_pad_ms":30}})
+        );
+    }
+
+    #[test]
+    fn test_profile_lifecycle() {
+        let dir = TempDir::new().unwrap();
+        let mut store = ProfileStore::load(dir.path()).unwrap();
+        store.create(" draft ", map(r#"{"whisper_model":"tiny"}"#), 1).unwrap();
+        assert_eq!(store.create("draft", Map::new(), 2).unwrap_err().code(), "profile_exists");
+        assert_eq!(store.create("  ", Map::new(), 2).unwrap_err().code(), "profile_name_invalid");
+
+        store.duplicate("draft", "final", 3).unwrap();
+        store.active = Some("draft".to_string());
+        store.rename("draft", "quick", 4).unwrap();
+        assert_eq!(store.active.as_deref(), Some("quick"));
+        assert_eq!(store.rename("quick", "final", 5).unwrap_err().code(), "profile_exists");
+
+        store.save(dir.path()).unwrap();
+        let mut store = ProfileStore::load(dir.path()).unwrap();
+        assert_eq!(store.get("final").unwrap().settings, map(r#"{"whisper_model":"tiny"}"#));
+        store.delete("quick").unwrap();
+        assert_eq!(store.active, None);
+        assert_eq!(store.delete("quick").unwrap_err().code(), "profile_not_found");
+    }
+}
@@ -0,0 +1,416 @@
+// 敏感信息脱敏：识别手机号、邮箱、身份证号、银行卡号及自定义规则，
+// 在转写文本中替换为带类型的占位符，并记录对应时间段供音频消音使用
+use crate::transcript::{Segment, Transcript};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 消音区间前后各扩展的时长，弥补按字符位置估算时间的误差
+const SPAN_PADDING_MS: i64 = 250;
+// 有词级时间戳时误差较小，只做少量扩展
+const WORD_PADDING_MS: i64 = 100;
+// 消音提示音
+const BEEP_FREQUENCY: u32 = 1000;
+const BEEP_VOLUME: f32 = 0.2;
+
+// 用户自定义规则：label 用作占位符名称，pattern 为正则表达式
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct CustomPattern {
+    pub(crate) label: String,
+    pub(crate) pattern: String,
+}
+
+// 一处被脱敏内容对应的音频时间段
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct RedactionSpan {
+    pub(crate) start_ms: i64,
+    pub(crate) end_ms: i64,
+    pub(crate) kind: String,
+}
+
+// 音频脱敏方式，对应设置项 redaction_audio_mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AudioMode {
+    Beep,
+    Silence,
+}
+
+impl AudioMode {
+    pub(crate) fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "beep" => Some(Self::Beep),
+            "silence" => Some(Self::Silence),
+            _ => None,
+        }
+    }
+}
+
+// 文本中一处命中：按字符计的区间与类型
+#[derive(Debug, Clone, PartialEq)]
+struct Hit {
+    start: usize,
+    end: usize,
+    kind: String,
+}
+
+// 无法编译的自定义规则：index 为规则在列表中的位置（从 0 开始），detail 为正则库的错误原文
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InvalidPattern {
+    pub(crate) index: usize,
+    pub(crate) label: String,
+    pub(crate) pattern: String,
+    pub(crate) detail: String,
+}
+
+// 仅用于日志
+impl fmt::Display for InvalidPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "脱敏规则 \"{}\" 无效: {}", self.label, self.detail)
+    }
+}
+
+pub(crate) struct Redactor {
+    email: Regex,
+    digits: Regex,
+    custom: Vec<(String, Regex)>,
+}
+
+impl Redactor {
+    pub(crate) fn new(custom: &[CustomPattern]) -> Result<Self, InvalidPattern> {
+        let mut compiled = Vec::with_capacity(custom.len());
+        for (index, p) in custom.iter().enumerate() {
+            let label = p.label.trim().to_uppercase();
+            if label.is_empty() || p.pattern.trim().is_empty() {
+                continue;
+            }
+            let re = Regex::new(&p.pattern).map_err(|e| InvalidPattern {
+                index,
+                label: p.label.clone(),
+                pattern: p.pattern.clone(),
+                detail: e.to_string(),
+            })?;
+            compiled.push((label, re));
+        }
+        Ok(Self {
+            email: Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)*\.[A-Za-z]{2,}").unwrap(),
+            // 连续数字（允许空格与连字符分组），末尾的 X 用于身份证校验位
+            digits: Regex::new(r"\+?\d(?:[ \-]?\d)+[Xx]?").unwrap(),
+            custom: compiled,
+        })
+    }
+
+    // 返回按位置排序、互不重叠的命中（以字符为单位）
+    fn find(&self, text: &str) -> Vec<Hit> {
+        // 按优先级收集：自定义规则、邮箱、数字类；与已有命中重叠的丢弃
+        let mut kept: Vec<(usize, usize, String)> = Vec::new();
+        let mut keep = |start: usize, end: usize, kind: String| {
+            if start < end && !kept.iter().any(|(s, e, _)| start < *e && *s < end) {
+                kept.push((start, end, kind));
+            }
+        };
+        for (label, re) in &self.custom {
+            for m in re.find_iter(text) {
+                keep(m.start(), m.end(), label.clone());
+            }
+        }
+        for m in self.email.find_iter(text) {
+            keep(m.start(), m.end(), "EMAIL".to_string());
+        }
+        for m in self.digits.find_iter(text) {
+            if let Some((len, kind)) = classify_digits(m.as_str()) {
+                keep(m.start(), m.start() + len, kind.to_string());
+            }
+        }
+
+        kept.sort_by_key(|(s, _, _)| *s);
+        kept.into_iter()
+            .map(|(s, e, kind)| Hit {
+                start: text[..s].chars().count(),
+                end: text[..e].chars().count(),
+                kind,
+            })
+            .collect()
+    }
+
+    // 替换为 [PHONE]、[EMAIL] 之类的占位符
+    pub(crate) fn redact(&self, text: &str) -> String {
+        replace_hits(text, &self.find(text))
+    }
+}
+
+fn replace_hits(text: &str, hits: &[Hit]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    for hit in hits {
+        out.extend(&chars[pos..hit.start]);
+        out.push_str(&format!("[{}]", hit.kind));
+        pos = hit.end;
+    }
+    out.extend(&chars[pos..]);
+    out
+}
+
+// 判断一段数字属于哪类敏感信息，返回需要替换的字节长度
+fn classify_digits(candidate: &str) -> Option<(usize, &'static str)> {
+    let digits: String = candidate.chars().filter(|c| c.is_ascii_digit()).collect();
+    let has_x = candidate.ends_with(['X', 'x']);
+    if has_x && digits.len() == 17 && id_checksum_ok(&format!("{}X", digits)) {
+        return Some((candidate.len(), "ID"));
+    }
+    // 末尾的 X 不属于号码本身
+    let len = if has_x { candidate.len() - 1 } else { candidate.len() };
+    let international = candidate.starts_with('+');
+
+    if !international && digits.len() == 18 && id_checksum_ok(&digits) {
+        return Some((len, "ID"));
+    }
+    if !international && (13..=19).contains(&digits.len()) && luhn_ok(&digits) {
+        return Some((len, "CARD"));
+    }
+    let mobile = digits.len() == 11 && digits.starts_with('1') && matches!(digits.as_bytes()[1], b'3'..=b'9');
+    let landline = digits.starts_with('0') && (10..=12).contains(&digits.len());
+    let intl = international && (8..=15).contains(&digits.len());
+    if mobile || landline || intl {
+        return Some((len, "PHONE"));
+    }
+    None
+}
+
+// Luhn 校验（银行卡号）
+fn luhn_ok(digits: &str) -> bool {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let d = (b - b'0') as u32;
+            if i % 2 == 1 {
+                let d2 = d * 2;
+                if d2 > 9 { d2 - 9 } else { d2 }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// 18 位居民身份证号校验位（GB 11643）
+fn id_checksum_ok(id: &str) -> bool {
+    const WEIGHTS: [u32; 17] = [7, 9, 10, 5, 8, 4, 2, 1, 6, 3, 7, 9, 10, 5, 8, 4, 2];
+    const CHECK: &[u8] = b"10X98765432";
+    let bytes = id.as_bytes();
+    if bytes.len() != 18 || !bytes[..17].iter().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let sum: u32 = bytes[..17].iter().zip(WEIGHTS).map(|(b, w)| (b - b'0') as u32 * w).sum();
+    bytes[17].to_ascii_uppercase() == CHECK[(sum % 11) as usize]
+}
+
+// 按字符位置在段内线性估算时间段
+fn estimate_span(seg: &Segment, hit: &Hit, total_chars: usize) -> RedactionSpan {
+    let duration = (seg.end_ms - seg.start_ms).max(0);
+    let total = total_chars.max(1) as i64;
+    let start = seg.start_ms + duration * hit.start as i64 / total;
+    let end = seg.start_ms + duration * hit.end as i64 / total;
+    RedactionSpan {
+        start_ms: (start - SPAN_PADDING_MS).max(0),
+        end_ms: end + SPAN_PADDING_MS,
+        kind: hit.kind.clone(),
+    }
+}
+
+// 各词在段落文本中的字符区间；无法逐个对齐时返回 None
+fn word_char_ranges(seg: &Segment) -> Option<Vec<(usize, usize)>> {
+    let chars: Vec<char> = seg.text.chars().collect();
+    let mut pos = 0;
+    let mut ranges = Vec::with_capacity(seg.words.len());
+    for w in &seg.words {
+        let wc: Vec<char> = w.text.chars().collect();
+        if wc.is_empty() || wc.len() > chars.len() {
+            return None;
+        }
+        let start = (pos..=chars.len() - wc.len()).find(|&i| chars[i..i + wc.len()] == wc[..])?;
+        pos = start + wc.len();
+        ranges.push((start, pos));
+    }
+    Some(ranges)
+}
+
+// 有词级时间戳时按命中覆盖的词取时间段，同时替换词文本：
+// 第一个词改为占位符，其余被覆盖的词删除
+fn redact_words(seg: &mut Segment, hits: &[Hit], ranges: &[(usize, usize)]) -> Vec<RedactionSpan> {
+    let mut spans = Vec::new();
+    let mut removed = vec![false; seg.words.len()];
+    for hit in hits {
+        let covered: Vec<usize> = (0..ranges.len())
+            .filter(|&i| ranges[i].0 < hit.end && hit.start < ranges[i].1)
+            .collect();
+        let (Some(&first), Some(&last)) = (covered.first(), covered.last()) else {
+            continue;
+        };
+        spans.push(RedactionSpan {
+            start_ms: (seg.words[first].start_ms - WORD_PADDING_MS).max(0),
+            end_ms: seg.words[last].end_ms + WORD_PADDING_MS,
+            kind: hit.kind.clone(),
+        });
+        seg.words[first].text = format!("[{}]", hit.kind);
+        for &i in &covered[1..] {
+            removed[i] = true;
+        }
+    }
+    let mut idx = 0;
+    seg.words.retain(|_| {
+        idx += 1;
+        !removed[idx - 1]
+    });
+    spans
+}
+
+// 合并重叠的时间段，类型以先出现的为准
+fn merge_spans(mut spans: Vec<RedactionSpan>) -> Vec<RedactionSpan> {
+    spans.sort_by_key(|s| s.start_ms);
+    let mut merged: Vec<RedactionSpan> = Vec::new();
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start_ms <= last.end_ms => last.end_ms = last.end_ms.max(span.end_ms),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+// 对转写结果脱敏，返回替换的处数；时间段写入 transcript.redactions
+pub(crate) fn apply(transcript: &mut Transcript, redactor: &Redactor) -> usize {
+    let mut spans = Vec::new();
+    let mut count = 0;
+    for seg in transcript.segments.iter_mut() {
+        let hits = redactor.find(&seg.text);
+        if hits.is_empty() {
+            continue;
+        }
+        match word_char_ranges(seg) {
+            Some(ranges) if !ranges.is_empty() => spans.extend(redact_words(seg, &hits, &ranges)),
+            _ => {
+                let total_chars = seg.text.chars().count();
+                spans.extend(hits.iter().map(|h| estimate_span(seg, h, total_chars)));
+                // 词与文本无法对齐时，词文本同样不能保留原文
+                for w in seg.words.iter_mut() {
+                    w.text = redactor.redact(&w.text);
+                }
+            }
+        }
+        seg.text = replace_hits(&seg.text, &hits);
+        count += hits.len();
+    }
+    spans.append(&mut transcript.redactions);
+    transcript.redactions = merge_spans(spans);
+    count
+}
+
+// ffmpeg enable 表达式：任一区间内为 1
+fn enable_expr(spans: &[RedactionSpan]) -> String {
+    if spans.is_empty() {
+        return "0".to_string();
+    }
+    spans
+        .iter()
+        .map(|s| format!("between(t,{:.3},{:.3})", s.start_ms as f64 / 1000.0, s.end_ms as f64 / 1000.0))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+// 生成音频脱敏的 ffmpeg 参数：静音模式直接把区间音量置零，
+// 提示音模式再叠加一路只在区间内发声的正弦波
+pub(crate) fn ffmpeg_args(input: &str, output: &str, spans: &[RedactionSpan], mode: AudioMode) -> Vec<String> {
+    let expr = enable_expr(spans);
+    let mut args = vec!["-i".to_string(), input.to_string()];
+    match mode {
+        AudioMode::Silence => {
+            args.push("-af".to_string());
+            args.push(format!("volume=enable='{}':volume=0", expr));
+        }
+        AudioMode::Beep => {
+            args.push("-filter_complex".to_string());
+            args.push(format!(
+                "[0:a]volume=enable='{e}':volume=0[muted];\
+                 sine=frequency={f}:sample_rate=16000,volume={v},volume=enable='not({e})':volume=0[beep];\
+                 [muted][beep]amix=inputs=2:duration=first:normalize=0[out]",
+                e = expr,
+                f = BEEP_FREQUENCY,
+                v = BEEP_VOLUME
+            ));
+            args.push("-map".to_string());
+            args.push("[out]".to_string());
+        }
+    }
+    args.push(output.to_string());
+    args.push("-y".to_string());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Word;
+
+    #[test]
+    fn test_redact_builtin_detectors() {
+        let r = Redactor::new(&[]).unwrap();
+        assert_eq!(r.redact("我的手机是138 1234 5678，邮箱 zhang.san@example.com"), "我的手机是[PHONE]，邮箱 [EMAIL]");
+        // 身份证号需通过校验位，银行卡号需通过 Luhn 校验
+        assert_eq!(r.redact("身份证11010519491231002X"), "身份证[ID]");
+        assert_eq!(r.redact("卡号 4111-1111-1111-1111"), "卡号 [CARD]");
+        assert_eq!(r.redact("订单号 4111111111111112，金额 1000000 元"), "订单号 4111111111111112，金额 1000000 元");
+    }
+
+    #[test]
+    fn test_custom_pattern_and_spans() {
+        let custom = vec![CustomPattern { label: "order".to_string(), pattern: r"SO-\d{6}".to_string() }];
+        let r = Redactor::new(&custom).unwrap();
+        let mut t = Transcript {
+            language: Some("zh".to_string()),
+            segments: vec![Segment::new(10_000, 20_000, "订单SO-123456已发货".to_string())],
+            ..Default::default()
+        };
+        assert_eq!(apply(&mut t, &r), 1);
+        assert_eq!(t.segments[0].text, "订单[ORDER]已发货");
+        // 命中位于第 2-11 个字符（共 14 个），按比例估算并前后扩展
+        assert_eq!(t.redactions.len(), 1);
+        assert_eq!(t.redactions[0].start_ms, 10_000 + 10_000 * 2 / 14 - SPAN_PADDING_MS);
+        assert_eq!(t.redactions[0].end_ms, 10_000 + 10_000 * 11 / 14 + SPAN_PADDING_MS);
+
+        let bad = vec![custom[0].clone(), CustomPattern { label: "bad".to_string(), pattern: "(".to_string() }];
+        let err = Redactor::new(&bad).err().unwrap();
+        assert_eq!((err.index, err.label.as_str(), err.pattern.as_str()), (1, "bad", "("));
+    }
+
+    #[test]
+    fn test_spans_from_words() {
+        let r = Redactor::new(&[]).unwrap();
+        let word = |s: i64, e: i64, t: &str| Word { start_ms: s, end_ms: e, text: t.to_string(), probability: 0.9 };
+        let mut seg = Segment::new(0, 5000, "call 138-1234-5678 now".to_string());
+        seg.words = vec![word(0, 500, "call"), word(600, 1800, "138-1234-"), word(1800, 2600, "5678"), word(2700, 3000, "now")];
+        let mut t = Transcript { segments: vec![seg], ..Default::default() };
+        assert_eq!(apply(&mut t, &r), 1);
+        assert_eq!(t.segments[0].text, "call [PHONE] now");
+        assert_eq!(t.redactions[0].start_ms, 600 - WORD_PADDING_MS);
+        assert_eq!(t.redactions[0].end_ms, 2600 + WORD_PADDING_MS);
+        let words: Vec<&str> = t.segments[0].words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, vec!["call", "[PHONE]", "now"]);
+    }
+
+    #[test]
+    fn test_ffmpeg_args() {
+        let spans = merge_spans(vec![
+            RedactionSpan { start_ms: 1000, end_ms: 2000, kind: "PHONE".to_string() },
+            RedactionSpan { start_ms: 1500, end_ms: 2500, kind: "EMAIL".to_string() },
+        ]);
+        assert_eq!(spans.len(), 1);
+        let args = ffmpeg_args("in.wav", "out.wav", &spans, AudioMode::Silence);
+        assert_eq!(args[3], "volume=enable='between(t,1.000,2.500)':volume=0");
+        let args = ffmpeg_args("in.wav", "out.wav", &spans, AudioMode::Beep);
+        assert!(args[3].contains("volume=enable='not(between(t,1.000,2.500))':volume=0[beep]"));
+    }
+}
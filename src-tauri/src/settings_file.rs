@@ -0,0 +1,227 @@
+// 设置文件读写：按 schema_version 逐步迁移；保存时先写临时文件再改名，并备份上一份可用的文件；
+// 读取时文件损坏则改用备份，个别字段无法解析时只把这些字段恢复为默认值
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::Write;
+use std::path::Path;
+
+pub(crate) const SETTINGS_FILE: &str = "settings.json";
+pub(crate) const BACKUP_FILE: &str = "settings.json.bak";
+const VERSION_KEY: &str = "schema_version";
+
+// 当前的设置结构版本，每新增一个迁移步骤加 1
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+type Migration = fn(&mut Map<String, Value>);
+
+// MIGRATIONS[i] 把版本 i 的设置迁移到版本 i + 1
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1];
+
+// 版本 0 为未记录版本号的旧文件：空字符串路径表示未设置，线程数可能被写成小数或字符串
+fn migrate_v0_to_v1(map: &mut Map<String, Value>) {
+    for key in ["whisper_models_path", "temp_dir_path", "speaker_model_path"] {
+        if map.get(key).and_then(Value::as_str).is_some_and(|s| s.trim().is_empty()) {
+            map.insert(key.to_string(), Value::Null);
+        }
+    }
+    let thread_count = match map.get("thread_count") {
+        Some(Value::Number(n)) if !n.is_u64() => n.as_f64(),
+        Some(Value::String(s)) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    if let Some(n) = thread_count.filter(|n| n.is_finite() && *n >= 0.0) {
+        map.insert("thread_count".to_string(), Value::from(n.round() as u64));
+    }
+}
+
+pub(crate) struct Loaded<T> {
+    pub(crate) settings: T,
+    // 文件中记录的版本，新文件或全新安装为当前版本
+    pub(crate) from_version: u32,
+    // 无法解析、已恢复为默认值的字段
+    pub(crate) recovered_fields: Vec<String>,
+    pub(crate) used_backup: bool,
+}
+
+impl<T> Loaded<T> {
+    // 迁移或恢复过的设置需要写回，使磁盘上的文件与内存一致
+    pub(crate) fn needs_save(&self) -> bool {
+        self.from_version < SCHEMA_VERSION || !self.recovered_fields.is_empty() || self.used_backup
+    }
+}
+
+fn version_of(map: &Map<String, Value>) -> u32 {
+    map.get(VERSION_KEY).and_then(Value::as_u64).map_or(0, |v| v as u32)
+}
+
+// 逐步迁移到当前版本；比当前版本新的文件（来自更新的程序）保持原样
+pub(crate) fn migrate(map: &mut Map<String, Value>) -> u32 {
+    let from = version_of(map);
+    for (version, step) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        step(map);
+        map.insert(VERSION_KEY.to_string(), Value::from(version as u32 + 1));
+    }
+    from
+}
+
+// 整体解析失败时逐个字段尝试，失败的字段保留默认值
+pub(crate) fn recover_fields<T: Serialize + DeserializeOwned + Default>(
+    map: &Map<String, Value>,
+) -> Result<(T, Vec<String>), String> {
+    if let Ok(settings) = serde_json::from_value::<T>(Value::Object(map.clone())) {
+        return Ok((settings, Vec::new()));
+    }
+    let mut merged = match serde_json::to_value(T::default()) {
+        Ok(Value::Object(m)) => m,
+        _ => return Err("默认设置无法序列化为对象".to_string()),
+    };
+    let mut recovered = Vec::new();
+    for (key, value) in map {
+        if !merged.contains_key(key) {
+            continue;
+        }
+        let previous = merged.insert(key.clone(), value.clone());
+        if serde_json::from_value::<T>(Value::Object(merged.clone())).is_err() {
+            if let Some(previous) = previous {
+                merged.insert(key.clone(), previous);
+            }
+            recovered.push(key.clone());
+        }
+    }
+    let settings = serde_json::from_value(Value::Object(merged)).map_err(|e| format!("恢复设置失败: {}", e))?;
+    Ok((settings, recovered))
+}
+
+fn read_object(path: &Path) -> Option<Map<String, Value>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(Value::Object(map)) => Some(map),
+        _ => None,
+    }
+}
+
+pub(crate) fn load<T: Serialize + DeserializeOwned + Default>(dir: &Path) -> Result<Loaded<T>, String> {
+    let path = dir.join(SETTINGS_FILE);
+    let backup = dir.join(BACKUP_FILE);
+    let (mut map, used_backup) = match read_object(&path) {
+        Some(map) => (map, false),
+        None => match read_object(&backup) {
+            Some(map) => (map, true),
+            // 全新安装，或文件与备份都不可用
+            None => {
+                let recovered = if path.exists() { vec![SETTINGS_FILE.to_string()] } else { Vec::new() };
+                return Ok(Loaded {
+                    settings: T::default(),
+                    from_version: SCHEMA_VERSION,
+                    recovered_fields: recovered,
+                    used_backup: false,
+                });
+            }
+        },
+    };
+    let from_version = migrate(&mut map);
+    let (settings, recovered_fields) = recover_fields(&map)?;
+    Ok(Loaded { settings, from_version, recovered_fields, used_backup })
+}
+
+// 写入临时文件并落盘后再改名替换，中途崩溃不会留下半个文件；替换前把当前可用的文件备份
+pub(crate) fn save<T: Serialize>(dir: &Path, settings: &T) -> Result<(), String> {
+    let mut value = serde_json::to_value(settings).map_err(|e| format!("序列化设置失败: {}", e))?;
+    if let Value::Object(map) = &mut value {
+        map.insert(VERSION_KEY.to_string(), Value::from(SCHEMA_VERSION));
+    }
+    let json = serde_json::to_string_pretty(&value).map_err(|e| format!("序列化设置失败: {}", e))?;
+
+    let path = dir.join(SETTINGS_FILE);
+    write_atomic(&path, json.as_bytes(), || {
+        if read_object(&path).is_some() {
+            if let Err(e) = std::fs::copy(&path, dir.join(BACKUP_FILE)) {
+                println!("备份设置失败: {}", e);
+            }
+        }
+    })
+    .map_err(|e| format!("写入设置失败: {}", e))
+}
+
+// 先写同目录下的临时文件并落盘，再改名替换目标文件；before_replace 在替换前执行（例如备份旧文件）
+pub(crate) fn write_atomic(path: &Path, content: &[u8], before_replace: impl FnOnce()) -> std::io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp = path.with_file_name(temp_name);
+    let written = std::fs::File::create(&temp).and_then(|mut f| {
+        f.write_all(content)?;
+        f.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e);
+    }
+    before_replace();
+    std::fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Sample {
+        #[serde(default)]
+        schema_version: u32,
+        whisper_models_path: Option<String>,
+        #[serde(default = "default_threads")]
+        thread_count: u32,
+        #[serde(default)]
+        enable_vad: bool,
+    }
+
+    fn default_threads() -> u32 { 4 }
+
+    impl Default for Sample {
+        fn default() -> Self {
+            Self { schema_version: SCHEMA_VERSION, whisper_models_path: None, thread_count: 4, enable_vad: false }
+        }
+    }
+
+    #[test]
+    fn test_migrate_and_recover() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(SETTINGS_FILE),
+            r#"{"whisper_models_path":"","thread_count":6.0,"enable_vad":"yes"}"#,
+        )
+        .unwrap();
+        let loaded: Loaded<Sample> = load(dir.path()).unwrap();
+        assert_eq!(loaded.from_version, 0);
+        assert_eq!(loaded.recovered_fields, vec!["enable_vad".to_string()]);
+        assert_eq!(
+            loaded.settings,
+            Sample { schema_version: 1, whisper_models_path: None, thread_count: 6, enable_vad: false }
+        );
+        assert!(loaded.needs_save());
+    }
+
+    #[test]
+    fn test_save_backup_and_corrupt_file() {
+        let dir = TempDir::new().unwrap();
+        let first = Sample { thread_count: 2, ..Sample::default() };
+        save(dir.path(), &first).unwrap();
+        save(dir.path(), &Sample { thread_count: 8, ..Sample::default() }).unwrap();
+        assert!(!dir.path().join("settings.json.tmp").exists());
+
+        // 写入中途崩溃留下的半个文件：改用上一份备份
+        std::fs::write(dir.path().join(SETTINGS_FILE), r#"{"thread_count": 8, "ena"#).unwrap();
+        let loaded: Loaded<Sample> = load(dir.path()).unwrap();
+        assert!(loaded.used_backup);
+        assert_eq!(loaded.settings, first);
+
+        // 备份也不可用时回退到默认设置
+        std::fs::write(dir.path().join(BACKUP_FILE), "garbage").unwrap();
+        let loaded: Loaded<Sample> = load(dir.path()).unwrap();
+        assert_eq!(loaded.settings, Sample::default());
+        assert_eq!(loaded.recovered_fields, vec![SETTINGS_FILE.to_string()]);
+    }
+}
@@ -0,0 +1,253 @@
+// 应用数据占用统计：按类别（转换音频、运行日志、转写结果、设置、模型、未完成下载、结果缓存）汇总大小，
+// 并支持按类别清理（可先预览）
+use crate::profiles::PROFILES_FILE;
+use crate::settings_file::{BACKUP_FILE, SETTINGS_FILE};
+use crate::workspace::jobs_root;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// 应用级运行日志（不属于某个任务的事件，例如启动时加载设置、回收旧任务），位于应用数据目录
+pub(crate) const APP_LOG_FILE: &str = "murmur.log";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Category {
+    Audio,
+    Logs,
+    Transcripts,
+    Settings,
+    Models,
+    Partial,
+    Cache,
+    Other,
+}
+
+pub(crate) const CATEGORIES: &[Category] = &[
+    Category::Audio,
+    Category::Logs,
+    Category::Transcripts,
+    Category::Settings,
+    Category::Models,
+    Category::Partial,
+    Category::Cache,
+    Category::Other,
+];
+
+// 统计范围：应用数据目录、临时目录（可能已迁移到其他磁盘）、位于应用数据目录内的模型目录
+pub(crate) struct StorageRoots {
+    pub(crate) app_dir: PathBuf,
+    pub(crate) temp_dirs: Vec<PathBuf>,
+    pub(crate) models_dir: Option<PathBuf>,
+    pub(crate) cache_dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct CategoryUsage {
+    pub(crate) category: Category,
+    pub(crate) size_bytes: u64,
+    pub(crate) file_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct CleanedFile {
+    pub(crate) path: String,
+    pub(crate) size_bytes: u64,
+    pub(crate) category: Category,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub(crate) struct CleanReport {
+    pub(crate) dry_run: bool,
+    pub(crate) files: Vec<CleanedFile>,
+    pub(crate) freed_bytes: u64,
+    // 属于正在使用的任务或删除失败的文件
+    pub(crate) skipped: Vec<String>,
+}
+
+const PARTIAL_EXTS: &[&str] = &["part", "partial", "download", "crdownload", "tmp"];
+const MEDIA_EXTS: &[&str] = &[
+    "wav", "mp3", "m4a", "flac", "aac", "ogg", "opus", "mp4", "mov", "mkv", "avi", "webm", "m4v",
+];
+const TRANSCRIPT_EXTS: &[&str] = &["json", "srt", "lrc", "txt", "ass"];
+// 任务目录中的记录文件，跟随任务一起由保留规则清理
+const JOB_FILES: &[&str] = &["job.json", ".active"];
+
+fn extension(path: &Path) -> String {
+    path.extension().and_then(|s| s.to_str()).unwrap_or("").to_ascii_lowercase()
+}
+
+pub(crate) fn classify(path: &Path, roots: &StorageRoots) -> Category {
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    let ext = extension(path);
+    if PARTIAL_EXTS.contains(&ext.as_str()) {
+        return Category::Partial;
+    }
+    if roots.models_dir.as_ref().is_some_and(|m| path.starts_with(m)) {
+        return Category::Models;
+    }
+    if path.starts_with(&roots.cache_dir) {
+        return Category::Cache;
+    }
+    // 只有任务目录（<临时目录>/jobs/）中的文件按类型归类，临时目录中的其他文件不参与清理
+    if roots.temp_dirs.iter().any(|t| path.starts_with(t)) {
+        if !roots.temp_dirs.iter().any(|t| path.starts_with(jobs_root(t))) {
+            return Category::Other;
+        }
+        return if JOB_FILES.contains(&name) {
+            Category::Other
+        } else if name.ends_with("_log.txt") {
+            Category::Logs
+        } else if MEDIA_EXTS.contains(&ext.as_str()) || name.contains(".overview-") {
+            // 波形概览缓存随音频生成，与音频一起清理
+            Category::Audio
+        } else if TRANSCRIPT_EXTS.contains(&ext.as_str()) {
+            Category::Transcripts
+        } else {
+            Category::Other
+        };
+    }
+    if path.parent() == Some(roots.app_dir.as_path()) && [SETTINGS_FILE, BACKUP_FILE, PROFILES_FILE].contains(&name) {
+        return Category::Settings;
+    }
+    if path.parent() == Some(roots.app_dir.as_path()) && name == APP_LOG_FILE {
+        return Category::Logs;
+    }
+    Category::Other
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<(PathBuf, u64)>) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                collect_files(&path, out);
+            } else if let Ok(metadata) = std::fs::metadata(&path) {
+                out.push((path, metadata.len()));
+            }
+        }
+    }
+}
+
+// 列出所有文件；应用数据目录外的临时目录只统计其中的任务目录，位于应用数据目录内时不重复统计
+pub(crate) fn scan(roots: &StorageRoots) -> Vec<(PathBuf, u64, Category)> {
+    let mut files = Vec::new();
+    collect_files(&roots.app_dir, &mut files);
+    for temp in &roots.temp_dirs {
+        if !temp.starts_with(&roots.app_dir) {
+            collect_files(&jobs_root(temp), &mut files);
+        }
+    }
+    files
+        .into_iter()
+        .map(|(path, size)| {
+            let category = classify(&path, roots);
+            (path, size, category)
+        })
+        .collect()
+}
+
+pub(crate) fn breakdown(roots: &StorageRoots) -> Vec<CategoryUsage> {
+    let mut usage: Vec<CategoryUsage> = CATEGORIES
+        .iter()
+        .map(|&category| CategoryUsage { category, size_bytes: 0, file_count: 0 })
+        .collect();
+    for (_, size, category) in scan(roots) {
+        if let Some(u) = usage.iter_mut().find(|u| u.category == category) {
+            u.size_bytes += size;
+            u.file_count += 1;
+        }
+    }
+    usage
+}
+
+// 删除所选类别的文件；protected 中的目录（正在使用的任务）不受影响
+pub(crate) fn clean(
+    roots: &StorageRoots,
+    categories: &[Category],
+    protected: &[PathBuf],
+    dry_run: bool,
+) -> CleanReport {
+    let mut report = CleanReport { dry_run, ..Default::default() };
+    for (path, size_bytes, category) in scan(roots) {
+        if !categories.contains(&category) {
+            continue;
+        }
+        let path_str = path.to_string_lossy().to_string();
+        if protected.iter().any(|p| path.starts_with(p)) {
+            report.skipped.push(path_str);
+            continue;
+        }
+        if dry_run || std::fs::remove_file(&path).is_ok() {
+            report.freed_bytes += size_bytes;
+            report.files.push(CleanedFile { path: path_str, size_bytes, category });
+        } else {
+            report.skipped.push(path_str);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, bytes: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; bytes]).unwrap();
+    }
+
+    #[test]
+    fn test_breakdown_and_clean() {
+        let app = TempDir::new().unwrap();
+        let external_temp = TempDir::new().unwrap();
+        let app_dir = app.path().to_path_buf();
+        let job = external_temp.path().join("jobs/1-1");
+        let active = external_temp.path().join("jobs/2-1");
+        write(&app_dir.join("settings.json"), 6);
+        write(&app_dir.join("settings.json.bak"), 4);
+        write(&app_dir.join("murmur.log"), 12);
+        write(&app_dir.join("models/ggml-base.bin"), 1000);
+        write(&app_dir.join("models/ggml-small.bin.part"), 300);
+        write(&job.join("job.json"), 5);
+        write(&job.join("a.wav"), 200);
+        write(&job.join("a.wav.overview-2000-50.json"), 20);
+        write(&job.join("a_log.txt"), 30);
+        write(&job.join("a.wav.transcript.json"), 40);
+        write(&job.join("a.wav.srt"), 8);
+        write(&active.join("b.wav"), 100);
+        // 临时目录中不属于任务的文件（以及应用数据目录外临时目录的旁邻文件）不归类为音频
+        write(&app_dir.join("temp/notes.mp3"), 7);
+        write(&external_temp.path().join("holiday.mp4"), 9);
+        write(&app_dir.join("cache/k1/audio.wav"), 60);
+
+        let roots = StorageRoots {
+            app_dir: app_dir.clone(),
+            temp_dirs: vec![external_temp.path().to_path_buf(), app_dir.join("temp")],
+            models_dir: Some(app_dir.join("models")),
+            cache_dir: app_dir.join("cache"),
+        };
+        let usage = breakdown(&roots);
+        let size_of = |c: Category| usage.iter().find(|u| u.category == c).unwrap().size_bytes;
+        assert_eq!(size_of(Category::Audio), 320);
+        assert_eq!(size_of(Category::Logs), 42);
+        assert_eq!(size_of(Category::Transcripts), 48);
+        assert_eq!(size_of(Category::Settings), 10);
+        assert_eq!(size_of(Category::Models), 1000);
+        assert_eq!(size_of(Category::Partial), 300);
+        assert_eq!(size_of(Category::Cache), 60);
+        assert_eq!(size_of(Category::Other), 12);
+
+        let protected = vec![active.clone()];
+        let preview = clean(&roots, &[Category::Audio, Category::Partial], &protected, true);
+        assert_eq!(preview.freed_bytes, 520);
+        assert_eq!(preview.skipped.len(), 1);
+        assert!(job.join("a.wav").exists());
+
+        let report = clean(&roots, &[Category::Audio, Category::Partial], &protected, false);
+        assert_eq!(report.files.len(), 3);
+        assert!(!job.join("a.wav").exists());
+        assert!(job.join("a.wav.srt").exists());
+        assert!(active.join("b.wav").exists());
+    }
+}
@@ -0,0 +1,596 @@
+// 识别结果的数据模型：解析 whisper-cli 的 JSON 输出，并统一生成 SRT/LRC/TXT
+use crate::error::MurmurError;
+use crate::i18n::Messages;
+use crate::redaction::RedactionSpan;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct Segment {
+    pub(crate) start_ms: i64,
+    pub(crate) end_ms: i64,
+    pub(crate) text: String,
+    // 后处理阶段给出的标记（例如疑似幻觉），每条附带原因
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) flags: Vec<SegmentFlag>,
+    // 词级时间戳与置信度，仅在使用 --output-json-full 时可用
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) words: Vec<Word>,
+    // 说话人编号，显示名称见 Transcript.speakers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) speaker: Option<String>,
+}
+
+// 中日韩文字按单字成词，其余语言按空格分词；probability 为所含 token 的平均概率
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct Word {
+    pub(crate) start_ms: i64,
+    pub(crate) end_ms: i64,
+    pub(crate) text: String,
+    pub(crate) probability: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct SegmentFlag {
+    pub(crate) kind: String,
+    #[serde(deserialize_with = "deserialize_reason")]
+    pub(crate) reason: FlagReason,
+}
+
+// 标记原因：code 为稳定的原因码，其余字段是前端本地化文案所需的参数
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub(crate) enum FlagReason {
+    // 与前一段相同，count 为连续出现的次数
+    RepeatPrevious { count: usize },
+    // 段内同一短语循环
+    RepeatInner { phrase: String, count: usize },
+    KnownPhrase { phrase: String },
+    Silence { peak_db: f32 },
+    TooShort { duration_ms: i64 },
+    // unit: "chars"（中日韩按字）或 "words"
+    SpeechRate { rate: f64, limit: f64, unit: String },
+    LowConfidence { words: Vec<Word> },
+    // 旧版本保存的文字说明
+    Legacy { text: String },
+}
+
+fn deserialize_reason<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FlagReason, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Coded(FlagReason),
+        Text(String),
+    }
+    Ok(match Repr::deserialize(deserializer)? {
+        Repr::Coded(reason) => reason,
+        Repr::Text(text) => FlagReason::Legacy { text },
+    })
+}
+
+impl Segment {
+    pub(crate) fn new(start_ms: i64, end_ms: i64, text: String) -> Self {
+        Self { start_ms, end_ms, text, flags: Vec::new(), words: Vec::new(), speaker: None }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct Transcript {
+    pub(crate) language: Option<String>,
+    pub(crate) segments: Vec<Segment>,
+    // 脱敏内容对应的音频时间段，用于生成消音后的音频
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) redactions: Vec<RedactionSpan>,
+    // 说话人编号 -> 显示名称，可在每个任务中单独重命名
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) speakers: BTreeMap<String, String>,
+}
+
+impl Transcript {
+    // 先登记编号，名称留空，由 default_speaker_names 按界面语言填写
+    pub(crate) fn add_speaker(&mut self, id: &str) {
+        self.speakers.entry(id.to_string()).or_default();
+    }
+
+    // 未命名的说话人使用本地化的默认名称，例如 "说话人 1" / "Speaker 1"
+    pub(crate) fn default_speaker_names(&mut self, messages: &Messages) {
+        for (id, name) in self.speakers.iter_mut().filter(|(_, name)| name.is_empty()) {
+            *name = messages.format("speaker.default_name", &[("id", id)]);
+        }
+    }
+
+    // 按编号顺序设置名称（names[0] 对应说话人 1），空名称保持默认
+    pub(crate) fn name_speakers(&mut self, names: &[String]) {
+        for (i, name) in names.iter().enumerate() {
+            let id = (i + 1).to_string();
+            let name = name.trim();
+            if !name.is_empty() {
+                if let Some(current) = self.speakers.get_mut(&id) {
+                    *current = name.to_string();
+                }
+            }
+        }
+    }
+
+    // 导出用文本：有说话人时加上 "名称: " 前缀
+    pub(crate) fn display_text(&self, seg: &Segment) -> String {
+        match &seg.speaker {
+            Some(id) => {
+                let name = self.speakers.get(id).map(|s| s.as_str()).filter(|s| !s.is_empty()).unwrap_or(id);
+                format!("{}: {}", name, seg.text)
+            }
+            None => seg.text.clone(),
+        }
+    }
+}
+
+// 中日韩文字（含假名与谚文）
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF)
+}
+
+// 取语言代码的主标签，例如 "zh-CN" -> "zh"
+pub(crate) fn primary_language(language: &str) -> String {
+    language.split(['-', '_']).next().unwrap_or(language).to_ascii_lowercase()
+}
+
+// 与 whisper-cli 的输出命名保持一致：<音频路径>.<扩展名>
+pub(crate) fn output_path(audio_file_path: &str, ext: &str) -> PathBuf {
+    PathBuf::from(format!("{}.{}", audio_file_path, ext))
+}
+
+// 后处理后的转写结果单独保存，避免与 whisper 原始 JSON 混淆
+pub(crate) fn transcript_path(audio_file_path: &str) -> PathBuf {
+    output_path(audio_file_path, "transcript.json")
+}
+
+// 口语清理前的逐字稿使用独立前缀：<音频路径>.verbatim.<扩展名>
+pub(crate) fn verbatim_base(audio_file_path: &str) -> String {
+    format!("{}.verbatim", audio_file_path)
+}
+
+// tinydiarize 在文本中插入的话轮标记
+const SPEAKER_TURN_MARKER: &str = "[SPEAKER_TURN]";
+
+// whisper.cpp 按 token 原样输出字节，多字节字符可能被拆到相邻 token 中，
+// 整个文件不一定是合法 UTF-8；这里把非法字节映射到私用区字符，组词时再还原
+const RAW_BYTE_BASE: u32 = 0xF700;
+
+pub(crate) fn read_whisper_json(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("读取 whisper JSON 失败: {}", e))?;
+    let mut out = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        out.push_str(chunk.valid());
+        for b in chunk.invalid() {
+            out.extend(char::from_u32(RAW_BYTE_BASE + *b as u32));
+        }
+    }
+    Ok(out)
+}
+
+fn raw_bytes(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let v = c as u32;
+        if (RAW_BYTE_BASE..RAW_BYTE_BASE + 0x100).contains(&v) {
+            out.push((v - RAW_BYTE_BASE) as u8);
+        } else {
+            let mut buf = [0u8; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+    }
+    out
+}
+
+fn decode_raw(text: &str) -> String {
+    String::from_utf8_lossy(&raw_bytes(text)).into_owned()
+}
+
+struct Token {
+    bytes: Vec<u8>,
+    start_ms: i64,
+    end_ms: i64,
+    probability: f32,
+}
+
+// 正在拼接的词
+struct WordBuilder {
+    bytes: Vec<u8>,
+    start_ms: i64,
+    end_ms: i64,
+    probability_sum: f32,
+    tokens: usize,
+}
+
+impl WordBuilder {
+    fn new(token: &Token) -> Self {
+        Self {
+            bytes: token.bytes.clone(),
+            start_ms: token.start_ms,
+            end_ms: token.end_ms,
+            probability_sum: token.probability,
+            tokens: 1,
+        }
+    }
+
+    fn push(&mut self, token: &Token) {
+        self.bytes.extend_from_slice(&token.bytes);
+        self.end_ms = self.end_ms.max(token.end_ms);
+        self.probability_sum += token.probability;
+        self.tokens += 1;
+    }
+
+    fn finish(self) -> Option<Word> {
+        let text = String::from_utf8_lossy(&self.bytes).trim().to_string();
+        if text.is_empty() {
+            return None;
+        }
+        Some(Word {
+            start_ms: self.start_ms,
+            end_ms: self.end_ms.max(self.start_ms),
+            text,
+            probability: self.probability_sum / self.tokens as f32,
+        })
+    }
+}
+
+// 只含标点的 token 并入前一个词
+fn is_punctuation(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => !s.is_empty() && s.chars().all(|c| !c.is_alphanumeric() && !c.is_whitespace()),
+        Err(_) => false,
+    }
+}
+
+// 新词的起点：前导空格，或当前词已是完整的中日韩文字，或 token 以 3 字节及以上的字符开头
+fn starts_word(current: &WordBuilder, token: &Token) -> bool {
+    let Ok(text) = std::str::from_utf8(&current.bytes) else {
+        // 当前词还缺少字节，必须继续拼接
+        return false;
+    };
+    if is_punctuation(&token.bytes) {
+        return false;
+    }
+    let first = token.bytes.first().copied().unwrap_or(b' ');
+    first == b' ' || first >= 0xE0 || text.chars().last().is_some_and(is_cjk)
+}
+
+fn build_words(tokens: &[Token]) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut current: Option<WordBuilder> = None;
+    for token in tokens {
+        match current.as_mut() {
+            Some(c) if !starts_word(c, token) => c.push(token),
+            _ => {
+                if let Some(word) = current.take().and_then(WordBuilder::finish) {
+                    words.push(word);
+                }
+                current = Some(WordBuilder::new(token));
+            }
+        }
+    }
+    if let Some(word) = current.and_then(WordBuilder::finish) {
+        words.push(word);
+    }
+    words
+}
+
+// --output-json-full 的 token 列表；[_BEG_]、[_TT_xxx] 之类的特殊 token 跳过。
+// 启用 DTW 时 t_dtw（单位 10ms）比 offsets 更准确，优先作为起始时间
+fn parse_tokens(item: &serde_json::Value) -> Vec<Token> {
+    let Some(items) = item.get("tokens").and_then(|t| t.as_array()) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|t| {
+            let text = t.get("text").and_then(|v| v.as_str())?;
+            if text.starts_with("[_") || text.starts_with("<|") {
+                return None;
+            }
+            let offsets = t.get("offsets");
+            let mut start_ms = offsets.and_then(|o| o.get("from")).and_then(|v| v.as_i64()).unwrap_or(0);
+            let end_ms = offsets.and_then(|o| o.get("to")).and_then(|v| v.as_i64()).unwrap_or(start_ms);
+            if let Some(dtw) = t.get("t_dtw").and_then(|v| v.as_i64()).filter(|v| *v >= 0) {
+                start_ms = dtw * 10;
+            }
+            let probability = t.get("p").and_then(|v| v.as_f64()).unwrap_or(1.0) as f32;
+            Some(Token { bytes: raw_bytes(text), start_ms, end_ms, probability })
+        })
+        .collect()
+}
+
+// 解析 --output-json / --output-json-full 的内容，格式示例：
+// {"result":{"language":"zh"},"transcription":[{"offsets":{"from":0,"to":2000},"text":" 你好","tokens":[...]}]}
+pub(crate) fn parse_whisper_json(content: &str) -> Result<Transcript, String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("解析 whisper JSON 失败: {}", e))?;
+
+    let language = value
+        .get("result")
+        .and_then(|r| r.get("language"))
+        .and_then(|l| l.as_str())
+        .map(|s| s.to_string());
+
+    let items = value
+        .get("transcription")
+        .and_then(|t| t.as_array())
+        .ok_or("whisper JSON 中缺少 transcription 字段")?;
+
+    // tinydiarize 模型会在每段附带 speaker_turn_next；它只标记话轮切换而不区分身份，
+    // 这里按两人对话交替标注，用户可再按任务重命名
+    let tinydiarize = items.iter().any(|item| item.get("speaker_turn_next").is_some());
+    let mut speaker = 1;
+
+    let mut segments = Vec::with_capacity(items.len());
+    for item in items {
+        let offsets = item.get("offsets");
+        let start_ms = offsets.and_then(|o| o.get("from")).and_then(|v| v.as_i64()).unwrap_or(0);
+        let end_ms = offsets.and_then(|o| o.get("to")).and_then(|v| v.as_i64()).unwrap_or(start_ms);
+        let text = item.get("text").and_then(|v| v.as_str()).unwrap_or("");
+        let text = decode_raw(text).replace(SPEAKER_TURN_MARKER, "");
+        let mut segment = Segment::new(start_ms, end_ms, text.trim().to_string());
+        segment.words = build_words(&parse_tokens(item));
+        if let Some(channel) = item.get("speaker").and_then(|v| v.as_str()) {
+            // --diarize 的立体声判断：0 为左声道，1 为右声道，无法判断时为 "?"
+            segment.speaker = channel.parse::<u32>().ok().map(|c| (c + 1).to_string());
+        } else if tinydiarize {
+            segment.speaker = Some(speaker.to_string());
+            if item.get("speaker_turn_next").and_then(|v| v.as_bool()).unwrap_or(false) {
+                speaker = 3 - speaker;
+            }
+        }
+        segments.push(segment);
+    }
+
+    let mut transcript = Transcript { language, segments, ..Default::default() };
+    let ids: Vec<String> = transcript.segments.iter().filter_map(|s| s.speaker.clone()).collect();
+    for id in ids {
+        transcript.add_speaker(&id);
+    }
+    Ok(transcript)
+}
+
+// 低置信度词，供前端逐个跳转复核
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct UncertainWord {
+    pub(crate) segment_index: usize,
+    pub(crate) start_ms: i64,
+    pub(crate) end_ms: i64,
+    pub(crate) text: String,
+    pub(crate) probability: f32,
+}
+
+pub(crate) fn low_confidence_words(transcript: &Transcript, threshold: f32) -> Vec<UncertainWord> {
+    let mut out = Vec::new();
+    for (i, seg) in transcript.segments.iter().enumerate() {
+        for w in seg.words.iter().filter(|w| w.probability < threshold) {
+            out.push(UncertainWord {
+                segment_index: i,
+                start_ms: w.start_ms,
+                end_ms: w.end_ms,
+                text: w.text.clone(),
+                probability: w.probability,
+            });
+        }
+    }
+    out
+}
+
+// 给含有低置信度词的段落加上 low_confidence 标记，返回标记的段数
+pub(crate) fn flag_low_confidence(transcript: &mut Transcript, threshold: f32) -> usize {
+    let mut flagged = 0;
+    for seg in transcript.segments.iter_mut() {
+        let uncertain: Vec<Word> = seg.words.iter().filter(|w| w.probability < threshold).cloned().collect();
+        if !uncertain.is_empty() {
+            seg.flags.push(SegmentFlag {
+                kind: "low_confidence".to_string(),
+                reason: FlagReason::LowConfidence { words: uncertain },
+            });
+            flagged += 1;
+        }
+    }
+    flagged
+}
+
+pub(crate) fn load_transcript(path: &Path) -> Result<Transcript, MurmurError> {
+    let content = std::fs::read_to_string(path).map_err(|e| MurmurError::io(path, e))?;
+    serde_json::from_str(&content).map_err(|e| MurmurError::io(path, e))
+}
+
+pub(crate) fn save_transcript(path: &Path, transcript: &Transcript) -> Result<(), MurmurError> {
+    let json = serde_json::to_string_pretty(transcript).map_err(|e| MurmurError::Internal { detail: e.to_string() })?;
+    std::fs::write(path, json).map_err(|e| MurmurError::io(path, e))
+}
+
+// 00:01:35,320
+fn format_srt_time(ms: i64) -> String {
+    let ms = ms.max(0);
+    let hours = ms / 3_600_000;
+    let minutes = (ms % 3_600_000) / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    let millis = ms % 1000;
+    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, seconds, millis)
+}
+
+// 01:35.32
+pub(crate) fn format_lrc_timestamp(ms: i64) -> String {
+    let ms = ms.max(0);
+    let minutes = ms / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    let centis = (ms % 1000) / 10;
+    format!("{:02}:{:02}.{:02}", minutes, seconds, centis)
+}
+
+// [01:35.32]
+fn format_lrc_time(ms: i64) -> String {
+    format!("[{}]", format_lrc_timestamp(ms))
+}
+
+pub(crate) fn render_srt(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    for (i, seg) in transcript.segments.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_srt_time(seg.start_ms),
+            format_srt_time(seg.end_ms),
+            transcript.display_text(seg)
+        ));
+    }
+    out
+}
+
+// 与 whisper-cli --output-lrc 的格式保持一致
+pub(crate) fn render_lrc(transcript: &Transcript) -> String {
+    let mut out = String::from("[by:whisper.cpp]\n");
+    for seg in &transcript.segments {
+        out.push_str(&format_lrc_time(seg.start_ms));
+        out.push_str(&seg.text);
+        out.push('\n');
+    }
+    out
+}
+
+pub(crate) fn render_txt(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    for seg in &transcript.segments {
+        out.push_str(&transcript.display_text(seg));
+        out.push('\n');
+    }
+    out
+}
+
+// 由同一份转写结果重新生成所有导出文件，保证各格式内容一致
+pub(crate) fn write_exports(audio_file_path: &str, transcript: &Transcript) -> Result<(), MurmurError> {
+    let outputs = [
+        ("srt", render_srt(transcript)),
+        ("lrc", render_lrc(transcript)),
+        ("txt", render_txt(transcript)),
+    ];
+    for (ext, content) in outputs.iter() {
+        let path = output_path(audio_file_path, ext);
+        std::fs::write(&path, content).map_err(|e| MurmurError::io(&path, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Transcript {
+        Transcript {
+            language: Some("zh".to_string()),
+            segments: vec![
+                Segment::new(0, 2500, "你好".to_string()),
+                Segment::new(95_320, 3_700_010, "世界".to_string()),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_whisper_json() {
+        let content = r#"{"result":{"language":"zh"},"transcription":[
+            {"timestamps":{"from":"00:00:00,000","to":"00:00:02,000"},"offsets":{"from":0,"to":2000},"text":" 你好"}
+        ]}"#;
+        let t = parse_whisper_json(content).unwrap();
+        assert_eq!(t.language.as_deref(), Some("zh"));
+        assert_eq!(t.segments.len(), 1);
+        assert_eq!(t.segments[0].end_ms, 2000);
+        assert_eq!(t.segments[0].text, "你好");
+    }
+
+    #[test]
+    fn test_tinydiarize_speakers() {
+        let content = r#"{"result":{"language":"en"},"transcription":[
+            {"offsets":{"from":0,"to":1000},"text":" Hi there. [SPEAKER_TURN]","speaker_turn_next":true},
+            {"offsets":{"from":1000,"to":2000},"text":" Hello.","speaker_turn_next":false},
+            {"offsets":{"from":2000,"to":3000},"text":" How are you?","speaker_turn_next":true},
+            {"offsets":{"from":3000,"to":4000},"text":" Fine.","speaker_turn_next":false}
+        ]}"#;
+        let mut t = parse_whisper_json(content).unwrap();
+        let speakers: Vec<&str> = t.segments.iter().map(|s| s.speaker.as_deref().unwrap()).collect();
+        assert_eq!(speakers, vec!["1", "2", "2", "1"]);
+        assert_eq!(t.segments[0].text, "Hi there.");
+
+        t.default_speaker_names(&Messages::new("en"));
+        assert_eq!(t.speakers["1"], "Speaker 1");
+        t.speakers.insert("2".to_string(), "Alice".to_string());
+        let txt = render_txt(&t);
+        assert!(txt.starts_with("Speaker 1: Hi there.\nAlice: Hello.\n"));
+        assert!(render_srt(&t).contains("00:00:03,000 --> 00:00:04,000\nSpeaker 1: Fine."));
+    }
+
+    #[test]
+    fn test_stereo_diarize_speakers() {
+        let content = r#"{"result":{"language":"zh"},"transcription":[
+            {"offsets":{"from":0,"to":1000},"text":"您好，请问有什么可以帮您","speaker":"0"},
+            {"offsets":{"from":1000,"to":2000},"text":"我想查询账单","speaker":"1"},
+            {"offsets":{"from":2000,"to":3000},"text":"好的","speaker":"?"}
+        ]}"#;
+        let mut t = parse_whisper_json(content).unwrap();
+        t.name_speakers(&["坐席".to_string(), "客户".to_string()]);
+        assert_eq!(render_txt(&t), "坐席: 您好，请问有什么可以帮您\n客户: 我想查询账单\n好的\n");
+    }
+
+    #[test]
+    fn test_parse_full_json_words() {
+        // "好"（E5 A5 BD）被拆到两个 token 中，文件本身不是合法 UTF-8
+        let mut content = Vec::new();
+        content.extend_from_slice(r#"{"result":{"language":"zh"},"transcription":[{"offsets":{"from":0,"to":3000},
+            "text":" 你好 hello world.","tokens":[
+            {"text":"[_BEG_]","offsets":{"from":0,"to":0},"p":0.9,"t_dtw":-1},
+            {"text":" 你","offsets":{"from":0,"to":400},"p":0.9,"t_dtw":-1},
+            {"text":""#.as_bytes());
+        content.push(0xE5);
+        content.extend_from_slice(br#"","offsets":{"from":400,"to":600},"p":0.8,"t_dtw":-1},
+            {"text":""#);
+        content.extend_from_slice(&[0xA5, 0xBD]);
+        content.extend_from_slice(br#"","offsets":{"from":600,"to":800},"p":0.6,"t_dtw":-1},
+            {"text":" hel","offsets":{"from":1000,"to":1300},"p":0.9,"t_dtw":110},
+            {"text":"lo","offsets":{"from":1300,"to":1500},"p":0.5,"t_dtw":-1},
+            {"text":" world","offsets":{"from":1600,"to":2200},"p":0.3,"t_dtw":-1},
+            {"text":".","offsets":{"from":2200,"to":2300},"p":0.9,"t_dtw":-1}
+        ]}]}"#);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("a.json");
+        std::fs::write(&path, content).unwrap();
+
+        let t = parse_whisper_json(&read_whisper_json(&path).unwrap()).unwrap();
+        let words: Vec<&str> = t.segments[0].words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, vec!["你", "好", "hello", "world."]);
+        let hao = &t.segments[0].words[1];
+        assert_eq!((hao.start_ms, hao.end_ms), (400, 800));
+        assert!((hao.probability - 0.7).abs() < 1e-6);
+        // t_dtw 优先作为起始时间
+        assert_eq!(t.segments[0].words[2].start_ms, 1100);
+
+        let uncertain = low_confidence_words(&t, 0.65);
+        assert_eq!(uncertain.len(), 1);
+        assert_eq!(uncertain[0].text, "world.");
+        let mut t = t;
+        assert_eq!(flag_low_confidence(&mut t, 0.75), 1);
+        assert_eq!(t.segments[0].flags[0].kind, "low_confidence");
+
+        // 原因以原因码保存，旧版本的文字说明仍可读取
+        let flag = serde_json::to_value(&t.segments[0].flags[0]).unwrap();
+        assert_eq!(flag["reason"]["code"], "low_confidence");
+        assert_eq!(flag["reason"]["words"].as_array().unwrap().len(), 3);
+        let legacy: SegmentFlag = serde_json::from_str(r#"{"kind":"silence","reason":"对应音频为静音"}"#).unwrap();
+        assert_eq!(legacy.reason, FlagReason::Legacy { text: "对应音频为静音".to_string() });
+    }
+
+    #[test]
+    fn test_render_srt_and_lrc() {
+        let t = sample();
+        let srt = render_srt(&t);
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:02,500\n你好\n\n"));
+        assert!(srt.contains("2\n00:01:35,320 --> 01:01:40,010\n世界"));
+        let lrc = render_lrc(&t);
+        assert!(lrc.contains("[01:35.32]世界"));
+    }
+}
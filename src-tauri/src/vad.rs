@@ -0,0 +1,175 @@
+// whisper-cli 的 VAD 参数：阈值、最短语音/静音时长、最长语音时长、前后填充与采样重叠
+use crate::i18n::Messages;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub(crate) struct VadSettings {
+    // 语音概率阈值（0-1），越高越不容易把噪声当作语音
+    pub(crate) threshold: f32,
+    pub(crate) min_speech_duration_ms: u32,
+    pub(crate) min_silence_duration_ms: u32,
+    // None 表示不限制
+    pub(crate) max_speech_duration_s: Option<f32>,
+    pub(crate) speech_pad_ms: u32,
+    // 相邻片段的重叠时长（秒）
+    pub(crate) samples_overlap: f32,
+    // 自定义 VAD 模型文件，为空时使用内置的 ggml-silero-v5.1.2.bin
+    pub(crate) model_path: Option<String>,
+}
+
+// 与 whisper.cpp 的默认值一致
+impl Default for VadSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            min_speech_duration_ms: 250,
+            min_silence_duration_ms: 100,
+            max_speech_duration_s: None,
+            speech_pad_ms: 30,
+            samples_overlap: 0.1,
+            model_path: None,
+        }
+    }
+}
+
+// 校验失败的字段与原因，提示文案由调用方按界面语言生成
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum VadError {
+    OutOfRange { field: &'static str, min: f32, max: f32 },
+    MaxSpeechTooShort,
+}
+
+impl VadError {
+    pub(crate) fn field(&self) -> &'static str {
+        match self {
+            VadError::OutOfRange { field, .. } => field,
+            VadError::MaxSpeechTooShort => "vad.max_speech_duration_s",
+        }
+    }
+
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            VadError::OutOfRange { .. } => "out_of_range",
+            VadError::MaxSpeechTooShort => "invalid_value",
+        }
+    }
+
+    pub(crate) fn message(&self, messages: &Messages) -> String {
+        match self {
+            VadError::OutOfRange { min, max, .. } => messages.format("settings.out_of_range", &[("min", min), ("max", max)]),
+            VadError::MaxSpeechTooShort => messages.get("settings.vad_max_speech_too_short"),
+        }
+    }
+}
+
+fn check(value: f32, field: &'static str, min: f32, max: f32) -> Result<(), VadError> {
+    if value.is_finite() && (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(VadError::OutOfRange { field, min, max })
+    }
+}
+
+pub(crate) const PRESET_NAMES: &[&str] = &["default", "noisy", "sensitive"];
+
+impl VadSettings {
+    // noisy：嘈杂环境（教室、会场），提高阈值并忽略短促声响；
+    // sensitive：安静环境下的轻声语音，降低阈值避免漏字
+    pub(crate) fn preset(name: &str) -> Option<Self> {
+        let base = Self::default();
+        match name {
+            "default" => Some(base),
+            "noisy" => Some(Self {
+                threshold: 0.65,
+                min_speech_duration_ms: 400,
+                min_silence_duration_ms: 300,
+                max_speech_duration_s: Some(30.0),
+                speech_pad_ms: 100,
+                ..base
+            }),
+            "sensitive" => Some(Self {
+                threshold: 0.35,
+                min_speech_duration_ms: 150,
+                min_silence_duration_ms: 200,
+                speech_pad_ms: 60,
+                ..base
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), VadError> {
+        check(self.threshold, "vad.threshold", 0.0, 1.0)?;
+        check(self.min_speech_duration_ms as f32, "vad.min_speech_duration_ms", 0.0, 10_000.0)?;
+        check(self.min_silence_duration_ms as f32, "vad.min_silence_duration_ms", 0.0, 10_000.0)?;
+        if let Some(max) = self.max_speech_duration_s {
+            if !max.is_finite() || max * 1000.0 < self.min_speech_duration_ms as f32 {
+                return Err(VadError::MaxSpeechTooShort);
+            }
+        }
+        check(self.speech_pad_ms as f32, "vad.speech_pad_ms", 0.0, 2_000.0)?;
+        check(self.samples_overlap, "vad.samples_overlap", 0.0, 1.0)
+    }
+
+    // 模型路径由调用方解析后传入
+    pub(crate) fn to_args(&self, model_path: &str) -> Vec<String> {
+        let mut args = vec![
+            "--vad".to_string(),
+            "--vad-model".to_string(),
+            model_path.to_string(),
+            "--vad-threshold".to_string(),
+            self.threshold.to_string(),
+            "--vad-min-speech-duration-ms".to_string(),
+            self.min_speech_duration_ms.to_string(),
+            "--vad-min-silence-duration-ms".to_string(),
+            self.min_silence_duration_ms.to_string(),
+            "--vad-speech-pad-ms".to_string(),
+            self.speech_pad_ms.to_string(),
+            "--vad-samples-overlap".to_string(),
+            self.samples_overlap.to_string(),
+        ];
+        if let Some(max) = self.max_speech_duration_s {
+            args.push("--vad-max-speech-duration-s".to_string());
+            args.push(max.to_string());
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_valid() {
+        for name in PRESET_NAMES {
+            assert!(VadSettings::preset(name).unwrap().validate().is_ok(), "{}", name);
+        }
+        assert!(VadSettings::preset("unknown").is_none());
+    }
+
+    #[test]
+    fn test_validate_and_args() {
+        let mut vad = VadSettings { threshold: 1.5, ..Default::default() };
+        let err = vad.validate().unwrap_err();
+        assert_eq!((err.field(), err.code()), ("vad.threshold", "out_of_range"));
+        assert_eq!(err.message(&Messages::new("en")), "Value must be between 0 and 1");
+        vad.threshold = 0.6;
+        vad.max_speech_duration_s = Some(0.1);
+        assert_eq!(vad.validate().unwrap_err(), VadError::MaxSpeechTooShort);
+        vad.max_speech_duration_s = Some(20.0);
+        assert!(vad.validate().is_ok());
+
+        let args = vad.to_args("silero.bin");
+        assert_eq!(&args[..5], &["--vad", "--vad-model", "silero.bin", "--vad-threshold", "0.6"]);
+        assert_eq!(&args[args.len() - 2..], &["--vad-max-speech-duration-s", "20"]);
+    }
+
+    #[test]
+    fn test_partial_json_uses_defaults() {
+        let vad: VadSettings = serde_json::from_str(r#"{"threshold":0.7}"#).unwrap();
+        assert_eq!(vad.threshold, 0.7);
+        assert_eq!(vad.speech_pad_ms, 30);
+    }
+}
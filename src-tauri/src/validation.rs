@@ -0,0 +1,167 @@
+// 设置校验与规范化的公共部分：按字段收集错误与警告，统一处理路径写法。
+// errors 会阻止保存；读取时同样的问题会把字段恢复为默认值并记为警告
+use crate::i18n::Messages;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct FieldIssue {
+    pub(crate) field: String,
+    // 稳定的问题代码，前端可据此定位字段并本地化提示
+    pub(crate) code: String,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub(crate) struct Report {
+    pub(crate) errors: Vec<FieldIssue>,
+    pub(crate) warnings: Vec<FieldIssue>,
+}
+
+impl Report {
+    fn issue(field: &str, code: &str, message: String) -> FieldIssue {
+        FieldIssue { field: field.to_string(), code: code.to_string(), message }
+    }
+
+    pub(crate) fn warn(&mut self, field: &str, code: &str, message: String) {
+        self.warnings.push(Self::issue(field, code, message));
+    }
+
+    // lenient（读取时）只记为警告，调用方负责把字段恢复为默认值
+    pub(crate) fn reject(&mut self, lenient: bool, messages: &Messages, field: &str, code: &str, message: String) {
+        if lenient {
+            let message = format!("{} ({})", message, messages.get("settings.reset_to_default"));
+            self.warnings.push(Self::issue(field, code, message));
+        } else {
+            self.errors.push(Self::issue(field, code, message));
+        }
+    }
+
+    pub(crate) fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    // 保存失败时的错误说明：字段: 原因; ...
+    pub(crate) fn summary(&self) -> String {
+        self.errors
+            .iter()
+            .map(|i| format!("{}: {}", i.field, i.message))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+// 取值必须是 allowed 之一；不合法时返回 false
+pub(crate) fn check_choice(
+    report: &mut Report,
+    lenient: bool,
+    messages: &Messages,
+    field: &str,
+    value: &str,
+    allowed: &[&str],
+) -> bool {
+    if allowed.contains(&value) {
+        return true;
+    }
+    let message = messages.format("settings.invalid_choice", &[("value", &value), ("allowed", &allowed.join(", "))]);
+    report.reject(lenient, messages, field, "invalid_choice", message);
+    false
+}
+
+// 取值必须在 [min, max] 内；不合法时返回 false
+pub(crate) fn check_range(
+    report: &mut Report,
+    lenient: bool,
+    messages: &Messages,
+    field: &str,
+    value: f32,
+    min: f32,
+    max: f32,
+) -> bool {
+    if value.is_finite() && (min..=max).contains(&value) {
+        return true;
+    }
+    let message = messages.format("settings.out_of_range", &[("min", &min), ("max", &max)]);
+    report.reject(lenient, messages, field, "out_of_range", message);
+    false
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+// 统一路径写法：去掉首尾空白与引号，file:// URL 转为本地路径（含 %20 等转义），
+// Windows 的 "/C:/..." 去掉开头的 '/'；空字符串视为未设置
+pub(crate) fn normalize_path(raw: &str) -> Option<String> {
+    let mut path = raw.trim().trim_matches(|c| c == '"' || c == '\'').trim().to_string();
+    if let Some(rest) = path.strip_prefix("file://") {
+        // file://localhost/... 与 file:///... 等价
+        let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+        path = percent_decode(rest);
+        let bytes = path.as_bytes();
+        if bytes.len() > 2 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+            path.remove(0);
+        }
+    }
+    // 去掉末尾多余的分隔符，根目录除外
+    while path.len() > 1 && (path.ends_with('/') || path.ends_with('\\')) && !path.ends_with(":/") && !path.ends_with(":\\") {
+        path.pop();
+    }
+    (!path.is_empty()).then_some(path)
+}
+
+// 规范化可选路径字段，返回是否有变化
+pub(crate) fn normalize_optional_path(value: &mut Option<String>) -> bool {
+    let normalized = value.as_deref().and_then(normalize_path);
+    let changed = normalized != *value;
+    *value = normalized;
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("  \"/Users/me/models/\" ").as_deref(), Some("/Users/me/models"));
+        assert_eq!(normalize_path("file:///C:/Users/me/My%20Models").as_deref(), Some("C:/Users/me/My Models"));
+        assert_eq!(normalize_path("file:///home/me/models").as_deref(), Some("/home/me/models"));
+        assert_eq!(normalize_path("file://localhost/home/me").as_deref(), Some("/home/me"));
+        assert_eq!(normalize_path("/").as_deref(), Some("/"));
+        assert_eq!(normalize_path("C:\\").as_deref(), Some("C:\\"));
+        assert_eq!(normalize_path("  ''  "), None);
+
+        let mut value = Some("file:///tmp/x/".to_string());
+        assert!(normalize_optional_path(&mut value));
+        assert_eq!(value.as_deref(), Some("/tmp/x"));
+        assert!(!normalize_optional_path(&mut value));
+    }
+
+    #[test]
+    fn test_report_lenient() {
+        let messages = Messages::new("en");
+        let mut report = Report::default();
+        assert!(check_choice(&mut report, false, &messages, "whisper_optimization", "vulkan", &["none", "vulkan"]));
+        assert!(!check_choice(&mut report, false, &messages, "whisper_optimization", "metal", &["none", "vulkan"]));
+        assert!(!check_range(&mut report, true, &messages, "low_confidence_threshold", 1.5, 0.0, 1.0));
+        assert!(!report.is_ok());
+        assert_eq!(report.errors[0].code, "invalid_choice");
+        assert_eq!(report.warnings[0].field, "low_confidence_threshold");
+        assert!(report.summary().starts_with("whisper_optimization: "));
+    }
+}
@@ -0,0 +1,335 @@
+// 任务工作区：每个任务使用独立目录 temp/jobs/<任务ID>/ 存放转换后的音频、日志与转写结果，
+// 仍被引用的任务不会被清理；其余任务按保留规则（最近 N 个、最长天数、总大小上限）回收
+use crate::calculate_directory_size;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) const JOBS_DIR: &str = "jobs";
+const JOB_META: &str = "job.json";
+// 记录创建任务的进程 ID，供其他实例判断任务是否仍在使用
+const ACTIVE_MARKER: &str = ".active";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct JobMeta {
+    pub(crate) id: String,
+    pub(crate) source: String,
+    // Unix 时间戳（秒）
+    pub(crate) created_at: u64,
+    // 输入内容与预处理参数的缓存键，识别时据此查找结果缓存
+    #[serde(default)]
+    pub(crate) input_key: Option<String>,
+}
+
+// 保留规则，0 表示不限制
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetentionPolicy {
+    pub(crate) keep_last: usize,
+    pub(crate) max_age_days: u64,
+    pub(crate) max_total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct RemovedJob {
+    pub(crate) id: String,
+    pub(crate) size_bytes: u64,
+    // keep_last | max_age | max_total_size | legacy
+    pub(crate) reason: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub(crate) struct CleanupReport {
+    pub(crate) dry_run: bool,
+    pub(crate) removed: Vec<RemovedJob>,
+    pub(crate) freed_bytes: u64,
+    pub(crate) kept: usize,
+    pub(crate) skipped_active: usize,
+}
+
+// 本进程内的任务引用计数：转换完成后由前端持有（结果被替换或丢弃时调用 release_job 释放），
+// 识别过程中再额外持有一次
+#[derive(Default)]
+pub(crate) struct JobRegistry {
+    refs: Mutex<HashMap<String, usize>>,
+}
+
+impl JobRegistry {
+    pub(crate) fn acquire(&self, id: &str) {
+        let mut refs = self.refs.lock().unwrap();
+        *refs.entry(id.to_string()).or_insert(0) += 1;
+    }
+
+    pub(crate) fn release(&self, id: &str) {
+        let mut refs = self.refs.lock().unwrap();
+        if let Some(n) = refs.get_mut(id) {
+            *n -= 1;
+            if *n == 0 {
+                refs.remove(id);
+            }
+        }
+    }
+
+    pub(crate) fn is_held(&self, id: &str) -> bool {
+        self.refs.lock().unwrap().contains_key(id)
+    }
+}
+
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+pub(crate) fn jobs_root(temp_dir: &Path) -> PathBuf {
+    temp_dir.join(JOBS_DIR)
+}
+
+// 任务 ID：毫秒时间戳 + 进程 ID，同一毫秒内重复时追加序号
+pub(crate) fn create_job(
+    temp_dir: &Path,
+    source: &str,
+    input_key: Option<String>,
+) -> std::io::Result<(JobMeta, PathBuf)> {
+    let root = jobs_root(temp_dir);
+    std::fs::create_dir_all(&root)?;
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+    let base = format!("{}-{}", millis, std::process::id());
+    let mut id = base.clone();
+    let mut n = 1;
+    while root.join(&id).exists() {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    let dir = root.join(&id);
+    std::fs::create_dir_all(&dir)?;
+
+    let meta = JobMeta { id, source: source.to_string(), created_at: now_secs(), input_key };
+    let json = serde_json::to_string_pretty(&meta)?;
+    std::fs::write(dir.join(JOB_META), json)?;
+    std::fs::write(dir.join(ACTIVE_MARKER), std::process::id().to_string())?;
+    Ok((meta, dir))
+}
+
+// 把已有文件放入任务目录：优先硬链接，跨磁盘或文件系统不支持时复制
+pub(crate) fn link_or_copy(from: &Path, to: &Path) -> std::io::Result<()> {
+    if to.exists() {
+        std::fs::remove_file(to)?;
+    }
+    if std::fs::hard_link(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to).map(|_| ())
+}
+
+// 由任务目录中的文件路径（例如转换后的 wav）反查任务
+pub(crate) fn job_meta_of(path: &Path) -> Option<JobMeta> {
+    read_meta(path.parent()?)
+}
+
+pub(crate) fn job_id_of(path: &Path) -> Option<String> {
+    job_meta_of(path).map(|meta| meta.id)
+}
+
+fn read_meta(dir: &Path) -> Option<JobMeta> {
+    let content = std::fs::read_to_string(dir.join(JOB_META)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// 本进程创建的任务以引用计数为准；其他实例创建的任务在其进程存活期间视为使用中
+fn is_active(dir: &Path, id: &str, registry: &JobRegistry, is_alive: &dyn Fn(u32) -> bool) -> bool {
+    let Some(pid) = std::fs::read_to_string(dir.join(ACTIVE_MARKER))
+        .ok()
+        .and_then(|s| s.trim().parse::<u32>().ok())
+    else {
+        return false;
+    };
+    if pid == std::process::id() {
+        registry.is_held(id)
+    } else {
+        is_alive(pid)
+    }
+}
+
+// 正在使用的任务目录，供按类别清理时跳过
+pub(crate) fn active_job_dirs(
+    temp_dir: &Path,
+    registry: &JobRegistry,
+    is_alive: &dyn Fn(u32) -> bool,
+) -> Vec<PathBuf> {
+    std::fs::read_dir(jobs_root(temp_dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|dir| {
+                    read_meta(dir).is_some_and(|meta| is_active(dir, &meta.id, registry, is_alive))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 旧版本遗留的运行目录：默认临时目录下以毫秒时间戳命名，只含文件，且有转换音频或运行日志
+fn is_legacy_run_dir(dir: &Path) -> bool {
+    let name = dir.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    let mut has_artifact = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            return false;
+        }
+        let file = entry.file_name().to_string_lossy().to_string();
+        has_artifact |= file.ends_with("_log.txt") || path.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    }
+    has_artifact
+}
+
+// 按保留规则清理任务目录；legacy_dir 为默认临时目录，其中旧版本遗留的运行目录直接清理，
+// 用户指定的临时目录不做这一步，避免误删不属于本应用的目录
+pub(crate) fn cleanup(
+    temp_dir: &Path,
+    legacy_dir: Option<&Path>,
+    policy: RetentionPolicy,
+    registry: &JobRegistry,
+    is_alive: &dyn Fn(u32) -> bool,
+    dry_run: bool,
+) -> CleanupReport {
+    let mut report = CleanupReport { dry_run, ..Default::default() };
+    let remove = |dir: &Path, id: String, size_bytes: u64, reason: &str, report: &mut CleanupReport| {
+        if dry_run || std::fs::remove_dir_all(dir).is_ok() {
+            report.freed_bytes += size_bytes;
+            report.removed.push(RemovedJob { id, size_bytes, reason: reason.to_string() });
+        }
+    };
+
+    if let Some(Ok(entries)) = legacy_dir.map(std::fs::read_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() && is_legacy_run_dir(&path) {
+                let size = calculate_directory_size(&path);
+                remove(&path, entry.file_name().to_string_lossy().to_string(), size, "legacy", &mut report);
+            }
+        }
+    }
+
+    // (目录, 任务信息, 大小, 是否使用中)，按创建时间从新到旧排列
+    let mut jobs: Vec<(PathBuf, JobMeta, u64, bool)> = std::fs::read_dir(jobs_root(temp_dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let dir = entry.path();
+                    let meta = read_meta(&dir)?;
+                    let active = is_active(&dir, &meta.id, registry, is_alive);
+                    Some((dir.clone(), meta, calculate_directory_size(&dir), active))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    jobs.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at).then_with(|| b.1.id.cmp(&a.1.id)));
+
+    let now = now_secs();
+    let mut survivors = Vec::new();
+    for (index, (dir, meta, size, active)) in jobs.into_iter().enumerate() {
+        if active {
+            report.skipped_active += 1;
+            survivors.push((dir, meta, size, active));
+            continue;
+        }
+        let age_days = now.saturating_sub(meta.created_at) / 86_400;
+        if policy.keep_last > 0 && index >= policy.keep_last {
+            remove(&dir, meta.id, size, "keep_last", &mut report);
+        } else if policy.max_age_days > 0 && age_days >= policy.max_age_days {
+            remove(&dir, meta.id, size, "max_age", &mut report);
+        } else {
+            survivors.push((dir, meta, size, active));
+        }
+    }
+
+    // 总大小超限时从最旧的任务开始清理
+    let mut total: u64 = survivors.iter().map(|j| j.2).sum();
+    if policy.max_total_bytes > 0 {
+        while total > policy.max_total_bytes {
+            let Some(pos) = survivors.iter().rposition(|j| !j.3) else {
+                break;
+            };
+            let (dir, meta, size, _) = survivors.remove(pos);
+            total -= size;
+            remove(&dir, meta.id, size, "max_total_size", &mut report);
+        }
+    }
+    report.kept = survivors.len();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fake_job(temp: &Path, id: &str, age_days: u64, bytes: usize, pid: Option<u32>) {
+        let dir = jobs_root(temp).join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        let meta = JobMeta { id: id.to_string(), source: "a.mp3".to_string(), created_at: now_secs() - age_days * 86_400, input_key: None };
+        std::fs::write(dir.join(JOB_META), serde_json::to_string(&meta).unwrap()).unwrap();
+        std::fs::write(dir.join("a.wav"), vec![0u8; bytes]).unwrap();
+        if let Some(pid) = pid {
+            std::fs::write(dir.join(ACTIVE_MARKER), pid.to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_create_job_and_lookup() {
+        let temp = TempDir::new().unwrap();
+        let registry = JobRegistry::default();
+        let (meta, dir) = create_job(temp.path(), "会议.mp3", Some("k".to_string())).unwrap();
+        assert_eq!(job_id_of(&dir.join("会议.wav")).as_deref(), Some(meta.id.as_str()));
+        assert_eq!(job_meta_of(&dir.join("会议.wav")).unwrap().input_key.as_deref(), Some("k"));
+
+        // 本进程创建的任务只有被引用时才受保护
+        let policy = RetentionPolicy { keep_last: 0, max_age_days: 0, max_total_bytes: 1 };
+        std::fs::write(dir.join("会议.wav"), b"data").unwrap();
+        registry.acquire(&meta.id);
+        let report = cleanup(temp.path(), None, policy, &registry, &|_| false, false);
+        assert_eq!(report.skipped_active, 1);
+        assert!(dir.exists());
+        registry.release(&meta.id);
+        let report = cleanup(temp.path(), None, policy, &registry, &|_| false, false);
+        assert_eq!(report.removed[0].reason, "max_total_size");
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_retention_rules() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("1700000000000")).unwrap();
+        std::fs::write(temp.path().join("1700000000000/a_log.txt"), b"log").unwrap();
+        // 不含本应用文件的数字目录（例如用户的 2024/）不属于旧版本遗留
+        std::fs::create_dir_all(temp.path().join("2024")).unwrap();
+        std::fs::write(temp.path().join("2024/photo.jpg"), b"jpg").unwrap();
+        fake_job(temp.path(), "j1", 0, 100, None);
+        fake_job(temp.path(), "j2", 1, 100, Some(u32::MAX));
+        fake_job(temp.path(), "j3", 2, 100, None);
+        fake_job(temp.path(), "j4", 10, 100, None);
+        fake_job(temp.path(), "j5", 20, 100, None);
+
+        let policy = RetentionPolicy { keep_last: 4, max_age_days: 7, max_total_bytes: 400 };
+        // 其他实例的进程仍存活：j2 受保护；每个任务约 170 字节（含 job.json）
+        let report = cleanup(temp.path(), Some(temp.path()), policy, &JobRegistry::default(), &|pid| pid == u32::MAX, true);
+        let removed: Vec<(&str, &str)> = report.removed.iter().map(|r| (r.id.as_str(), r.reason.as_str())).collect();
+        assert_eq!(
+            removed,
+            vec![("1700000000000", "legacy"), ("j4", "max_age"), ("j5", "keep_last"), ("j3", "max_total_size")]
+        );
+        assert_eq!(report.skipped_active, 1);
+        assert_eq!(report.kept, 2);
+        // 预览模式不删除任何文件
+        assert!(jobs_root(temp.path()).join("j5").exists());
+    }
+}